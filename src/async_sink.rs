@@ -0,0 +1,244 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/async_sink.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+use std::collections::VecDeque;
+use std::io;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+
+use crate::log_sink::LogSink;
+use crate::logger::Log;
+
+/// Что делать, когда очередь фонового писателя заполнена
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Ждать, пока в очереди освободится место
+    Block,
+    /// Отбросить новый лог
+    DropNewest,
+    /// Отбросить самый старый лог из очереди
+    DropOldest,
+}
+
+/// Состояние очереди, общее для логгера и фонового потока
+struct Queue {
+    entries: VecDeque<(Log, String)>,
+    capacity: usize,
+    closed: bool,
+    /// Фоновый поток завершился, например из-за паники в приёмнике
+    writer_stopped: bool,
+    dropped: u64,
+    flush_requested: u64,
+    flush_done: u64,
+    error: Option<io::Error>,
+}
+
+struct State {
+    queue: Mutex<Queue>,
+    /// Сигнал фоновому потоку: появились логи, запрошен сброс или закрытие
+    wake_writer: Condvar,
+    /// Сигнал писателям логов: освободилось место или завершён сброс
+    wake_producers: Condvar,
+}
+
+impl State {
+    fn lock(&self) -> MutexGuard<'_, Queue> {
+        self.queue.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Приёмник, передающий логи другому приёмнику через фоновый поток
+///
+/// `write_log` только кладёт лог в ограниченную очередь, запись идёт в фоне.
+/// `flush` и `Drop` дожидаются записи всей очереди.
+pub struct AsyncSink {
+    state: Arc<State>,
+    policy: OverflowPolicy,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl AsyncSink {
+    /// Запуск фонового потока для приёмника с очередью на `capacity` логов
+    pub fn new(sink: impl LogSink + 'static, capacity: usize, policy: OverflowPolicy) -> Self {
+        let state = Arc::new(State {
+            queue: Mutex::new(Queue {
+                entries: VecDeque::with_capacity(capacity),
+                capacity: capacity.max(1),
+                closed: false,
+                writer_stopped: false,
+                dropped: 0,
+                flush_requested: 0,
+                flush_done: 0,
+                error: None,
+            }),
+            wake_writer: Condvar::new(),
+            wake_producers: Condvar::new(),
+        });
+
+        let worker_state = Arc::clone(&state);
+        let worker = thread::Builder::new()
+            .name("prettyterm-writer".to_string())
+            .spawn(move || run_writer(worker_state, Box::new(sink)))
+            .expect("failed to spawn log writer thread");
+
+        Self {
+            state,
+            policy,
+            worker: Some(worker),
+        }
+    }
+
+    /// Сколько логов было отброшено из-за переполнения очереди
+    pub fn dropped(&self) -> u64 {
+        self.state.lock().dropped
+    }
+
+    /// Сколько логов ждут записи
+    pub fn pending(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+}
+
+impl LogSink for AsyncSink {
+    fn write_log(&mut self, log: &Log, line: &str) -> io::Result<()> {
+        let mut queue = self.state.lock();
+
+        if queue.entries.len() >= queue.capacity {
+            match self.policy {
+                OverflowPolicy::Block => {
+                    while queue.entries.len() >= queue.capacity && !queue.writer_stopped {
+                        queue = self
+                            .state
+                            .wake_producers
+                            .wait(queue)
+                            .unwrap_or_else(|poisoned| poisoned.into_inner());
+                    }
+                }
+                OverflowPolicy::DropNewest => {
+                    queue.dropped += 1;
+                    return take_error(&mut queue);
+                }
+                OverflowPolicy::DropOldest => {
+                    queue.entries.pop_front();
+                    queue.dropped += 1;
+                }
+            }
+        }
+
+        if queue.writer_stopped {
+            return Err(writer_stopped());
+        }
+
+        queue.entries.push_back((log.clone(), line.to_string()));
+        self.state.wake_writer.notify_one();
+        take_error(&mut queue)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut queue = self.state.lock();
+        queue.flush_requested += 1;
+        let ticket = queue.flush_requested;
+        self.state.wake_writer.notify_one();
+
+        while queue.flush_done < ticket && !queue.writer_stopped {
+            queue = self
+                .state
+                .wake_producers
+                .wait(queue)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+
+        if queue.flush_done < ticket {
+            return Err(writer_stopped());
+        }
+        take_error(&mut queue)
+    }
+}
+
+impl Drop for AsyncSink {
+    fn drop(&mut self) {
+        self.state.lock().closed = true;
+        self.state.wake_writer.notify_one();
+
+        // Фоновый поток дописывает очередь перед завершением
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn take_error(queue: &mut Queue) -> io::Result<()> {
+    match queue.error.take() {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+fn writer_stopped() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "log writer thread has stopped")
+}
+
+/// Отметка о завершении фонового потока, срабатывает и при панике в приёмнике
+struct WriterGuard(Arc<State>);
+
+impl Drop for WriterGuard {
+    fn drop(&mut self) {
+        self.0.lock().writer_stopped = true;
+        self.0.wake_producers.notify_all();
+    }
+}
+
+/// Цикл фонового потока
+fn run_writer(state: Arc<State>, mut sink: Box<dyn LogSink>) {
+    let _guard = WriterGuard(Arc::clone(&state));
+    let mut queue = state.lock();
+
+    loop {
+        if let Some((log, line)) = queue.entries.pop_front() {
+            drop(queue);
+            state.wake_producers.notify_all();
+
+            let result = sink.write_log(&log, &line);
+
+            queue = state.lock();
+            if let Err(error) = result {
+                queue.error = Some(error);
+            }
+            continue;
+        }
+
+        // Очередь пуста: выполняем запрошенный сброс
+        if queue.flush_done < queue.flush_requested {
+            let ticket = queue.flush_requested;
+            drop(queue);
+            let result = sink.flush();
+
+            queue = state.lock();
+            if let Err(error) = result {
+                queue.error = Some(error);
+            }
+            queue.flush_done = ticket;
+            state.wake_producers.notify_all();
+            continue;
+        }
+
+        if queue.closed {
+            drop(queue);
+            let _ = sink.flush();
+            return;
+        }
+
+        queue = state
+            .wake_writer
+            .wait(queue)
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+    }
+}
@@ -0,0 +1,421 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/bin/prettyterm-log.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+//! Просмотр сохранённых файлов логов: фильтрация и вывод в выбранном стиле
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+
+use prettyterm::*;
+
+const USAGE: &str = "\
+Usage: prettyterm-log [OPTIONS] <FILE>...
+
+Reads log files written by prettyterm and prints them with theme colors.
+
+Options:
+  -s, --style <STYLE>        Output style: tiny, flat, full, json, logfmt (default: tiny)
+      --template <TEMPLATE>  Output with a custom template, e.g. \"{time} {status:>7} {message}\"
+      --input-style <STYLE>  Style of the input files (default: detected from the first line)
+      --status <LIST>        Only these statuses, e.g. error,warn
+      --min-status <STATUS>  Only logs at or above this status
+      --filter <DIRECTIVES>  Per-component thresholds, e.g. warn,parser=debug
+      --file <NAME>          Only logs from this source file
+      --func <NAME>          Only logs from this function
+      --dir <PATH>           Only logs from this directory and its subdirectories
+      --contains <TEXT>      Only logs whose message contains the text
+      --since <TIME>         Only logs at or after the time (RFC 3339 or HH:MM[:SS])
+      --until <TIME>         Only logs before the time
+      --time-format <FMT>    clock, precise, datetime, rfc3339 or a strftime string
+      --color <WHEN>         auto, always or never (default: auto)
+  -f, --follow               Keep reading as the file grows
+  -m, --merge                Interleave all files by time, tagging each log with its source file
+  -h, --help                 Print this help
+
+Times without a date are placed on the day the file was last modified.";
+
+/// Разобранные аргументы командной строки
+struct Options {
+    files: Vec<String>,
+    style: LoggerPrintStyle,
+    input_style: Option<LoggerPrintStyle>,
+    query: LogQuery,
+    since: Option<String>,
+    until: Option<String>,
+    filter: Option<LogFilter>,
+    time_format: TimeFormat,
+    color_mode: ColorMode,
+    follow: bool,
+    merge: bool,
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("prettyterm-log: {}\n\n{}", error, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut logger = Logger::new(LogTime::now(), true);
+    logger.style = options.style.clone();
+    logger.display_config = Some(DisplayConfig::default());
+    logger.color_mode = options.color_mode;
+    logger.time_format = options.time_format.clone();
+    logger.filter = options.filter.clone();
+
+    if options.merge {
+        return match view_merged(&options, &logger) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("prettyterm-log: {}", error);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let mut failed = false;
+    for path in &options.files {
+        if let Err(error) = view_file(path, &options, &logger) {
+            eprintln!("prettyterm-log: {}: {}", path, error);
+            failed = true;
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Разбор аргументов, `None` — запрошена справка
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        files: Vec::new(),
+        style: LoggerPrintStyle::Tiny,
+        input_style: None,
+        query: LogQuery::new(),
+        since: None,
+        until: None,
+        filter: None,
+        time_format: TimeFormat::Clock,
+        color_mode: ColorMode::Auto,
+        follow: false,
+        merge: false,
+    };
+
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        // Поддерживаем и `--style json`, и `--style=json`
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = || {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("missing value for '{}'", flag))
+        };
+
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-f" | "--follow" => options.follow = true,
+            "-m" | "--merge" => options.merge = true,
+            "-s" | "--style" => options.style = parse_style(&value()?)?,
+            "--template" => {
+                let template = LogTemplate::parse(&value()?).map_err(|error| error.to_string())?;
+                options.style = LoggerPrintStyle::Custom(template);
+            }
+            "--input-style" => options.input_style = Some(parse_style(&value()?)?),
+            "--status" => {
+                for name in value()?.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+                    let status = Status::from_name(name).map_err(|error| error.to_string())?;
+                    options.query = options.query.status(status);
+                }
+            }
+            "--min-status" => {
+                let status = value()?.parse().map_err(|error: ParseStatusError| error.to_string())?;
+                options.query = options.query.min_status(status);
+            }
+            "--filter" => {
+                options.filter = Some(LogFilter::parse(&value()?).map_err(|error| error.to_string())?)
+            }
+            "--file" => options.query = options.query.file(&value()?),
+            "--func" => options.query = options.query.func(&value()?),
+            "--dir" => options.query = options.query.dir(&value()?),
+            "--contains" => options.query = options.query.contains(&value()?),
+            "--since" => options.since = Some(value()?),
+            "--until" => options.until = Some(value()?),
+            "--time-format" => {
+                options.time_format = match value()?.as_str() {
+                    "clock" => TimeFormat::Clock,
+                    "precise" => TimeFormat::Precise,
+                    "datetime" => TimeFormat::DateTime,
+                    "rfc3339" => TimeFormat::Rfc3339,
+                    other => TimeFormat::Strftime(other.to_string()),
+                }
+            }
+            "--color" => {
+                options.color_mode = match value()?.as_str() {
+                    "auto" => ColorMode::Auto,
+                    "always" => ColorMode::Always,
+                    "never" => ColorMode::Never,
+                    other => return Err(format!("invalid color mode '{}'", other)),
+                }
+            }
+            _ if flag.starts_with('-') => return Err(format!("unknown option '{}'", flag)),
+            _ => options.files.push(arg),
+        }
+    }
+
+    if options.files.is_empty() {
+        return Err("no log files given".to_string());
+    }
+    if options.follow && (options.merge || options.files.len() > 1) {
+        return Err("--follow works with a single file".to_string());
+    }
+    Ok(Some(options))
+}
+
+fn parse_style(name: &str) -> Result<LoggerPrintStyle, String> {
+    match name.to_lowercase().as_str() {
+        "tiny" => Ok(LoggerPrintStyle::Tiny),
+        "flat" => Ok(LoggerPrintStyle::Flat),
+        "full" => Ok(LoggerPrintStyle::Full),
+        "json" => Ok(LoggerPrintStyle::Json),
+        "logfmt" => Ok(LoggerPrintStyle::Logfmt),
+        _ => Err(format!("unknown style '{}'", name)),
+    }
+}
+
+/// Запрос с границами `--since`/`--until`, время без даты относится ко дню `base_time`
+fn with_time_range(query: &LogQuery, options: &Options, base_time: LogTime) -> io::Result<LogQuery> {
+    let parser = LogParser::new(LoggerPrintStyle::Tiny).with_base_time(base_time);
+    let parse = |text: &String| {
+        parser
+            .parse_time(text)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, format!("invalid time '{}'", text)))
+    };
+
+    let mut query = query.clone();
+    if let Some(since) = &options.since {
+        query = query.since(parse(since)?);
+    }
+    if let Some(until) = &options.until {
+        query = query.until(parse(until)?);
+    }
+    Ok(query)
+}
+
+/// Вывод всех файлов одним потоком, упорядоченным по времени
+///
+/// Время без даты во всех файлах и в `--since`/`--until` относится ко дню
+/// самого позднего изменения среди файлов.
+fn view_merged(options: &Options, logger: &Logger) -> io::Result<()> {
+    let mut base_time = None;
+    for path in &options.files {
+        base_time = base_time.max(Some(file_base_time(path)?));
+    }
+    let base_time = base_time.unwrap_or_else(LogTime::now);
+
+    let mut merger = LogMerger::new();
+    for path in &options.files {
+        let source = match &options.input_style {
+            Some(style) => LogSource::read_with(path, &LogParser::new(style.clone()).with_base_time(base_time))?,
+            None => LogSource::read_at(path, base_time)?,
+        };
+        merger = merger.source(source);
+    }
+
+    for (name, error) in merger.errors() {
+        eprintln!("prettyterm-log: {}: {}", name, error);
+    }
+
+    let query = with_time_range(&options.query, options, base_time)?;
+    for log in merger.merge() {
+        if query.matches(&log) && logger.is_enabled(log.status, &log.component) {
+            println!("{}", logger.format_for_terminal(&log));
+        }
+    }
+    Ok(())
+}
+
+/// Вывод одного файла, при `--follow` — бесконечное чтение новых строк
+fn view_file(path: &str, options: &Options, logger: &Logger) -> io::Result<()> {
+    let file = File::open(path)?;
+
+    // Время без даты относим ко дню последнего изменения файла
+    let base_time = file_base_time(path)?;
+
+    let mut viewer = Viewer {
+        path,
+        logger,
+        query: with_time_range(&options.query, options, base_time)?,
+        parser: options
+            .input_style
+            .clone()
+            .map(|style| LogParser::new(style).with_base_time(base_time)),
+        base_time,
+        pending: None,
+        line_number: 0,
+    };
+
+    let mut identity = file_identity(&file.metadata()?);
+    let mut reader = BufReader::new(file);
+    let mut position = 0;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line)?;
+
+        // Неполная строка в конце растущего файла дочитывается позже
+        if read > 0 && (line.ends_with('\n') || !options.follow) {
+            position += read as u64;
+            viewer.push_line(line.trim_end_matches(['\n', '\r']));
+            continue;
+        }
+        if read > 0 {
+            reader.seek(SeekFrom::Start(position))?;
+        }
+
+        viewer.flush_pending();
+        if !options.follow {
+            return Ok(());
+        }
+
+        thread::sleep(Duration::from_millis(250));
+
+        // Файл усечён или заменён новым при ротации — читаем с начала.
+        // Пока новый файл не создан или не открывается, дочитываем старый.
+        let Ok(metadata) = std::fs::metadata(path) else {
+            continue;
+        };
+        let rotated = file_identity(&metadata) != identity;
+        if metadata.len() < position || rotated {
+            let Ok((new_file, new_metadata)) = File::open(path).and_then(|file| {
+                let metadata = file.metadata()?;
+                Ok((file, metadata))
+            }) else {
+                continue;
+            };
+
+            // Строки, дописанные в старый файл перед ротацией, не теряются
+            if rotated {
+                loop {
+                    line.clear();
+                    match reader.read_line(&mut line) {
+                        Ok(0) | Err(_) => break,
+                        Ok(_) => viewer.push_line(line.trim_end_matches(['\n', '\r'])),
+                    }
+                }
+                viewer.flush_pending();
+            }
+
+            identity = file_identity(&new_metadata);
+            reader = BufReader::new(new_file);
+            position = 0;
+            viewer.line_number = 0;
+        }
+    }
+}
+
+/// Устройство и inode файла: по ним видно, что путь указывает на другой файл
+#[cfg(unix)]
+fn file_identity(metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+/// Без inode ротация замечается только по уменьшению размера
+#[cfg(not(unix))]
+fn file_identity(_metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Состояние чтения одного файла
+struct Viewer<'a> {
+    path: &'a str,
+    logger: &'a Logger,
+    query: LogQuery,
+    parser: Option<LogParser>,
+    base_time: LogTime,
+    /// Незаконченная запись, к ней могут добавиться строки с отступом
+    pending: Option<(usize, String)>,
+    line_number: usize,
+}
+
+impl Viewer<'_> {
+    fn push_line(&mut self, line: &str) {
+        self.line_number += 1;
+        if line.trim().is_empty() {
+            return;
+        }
+
+        // Пока стиль не известен, строкой продолжения считается любая строка с отступом
+        let continues = match &self.parser {
+            Some(parser) => parser.is_continuation(line),
+            None => line.starts_with([' ', '\t']),
+        };
+        if continues {
+            if let Some((_, entry)) = &mut self.pending {
+                entry.push('\n');
+                entry.push_str(line);
+                return;
+            }
+        }
+        self.flush_pending();
+        self.pending = Some((self.line_number, line.to_string()));
+    }
+
+    fn flush_pending(&mut self) {
+        let Some((number, entry)) = self.pending.take() else {
+            return;
+        };
+
+        // Стиль определяется по первой записи целиком
+        if self.parser.is_none() {
+            match detect_style(&entry) {
+                Some(style) => self.parser = Some(LogParser::new(style).with_base_time(self.base_time)),
+                None => {
+                    eprintln!("prettyterm-log: {}:{}: unknown log format", self.path, number);
+                    return;
+                }
+            }
+        }
+        self.show(number, &entry);
+    }
+
+    fn show(&self, number: usize, entry: &str) {
+        let Some(parser) = &self.parser else {
+            return;
+        };
+
+        match parser.parse_entry(entry) {
+            Ok(log) => {
+                if self.query.matches(&log) && self.logger.is_enabled(log.status, &log.component) {
+                    println!("{}", self.logger.format_for_terminal(&log));
+                }
+            }
+            Err(error) => eprintln!("prettyterm-log: {}: {}", self.path, error.at_line(number)),
+        }
+    }
+}
@@ -0,0 +1,155 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/child_logger.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+use crate::common_types::{FieldValue, Status};
+use crate::logger::{Component, Log, LogTime, Logger};
+use crate::shared_logger::SharedLogger;
+
+/// Компонент и поля, которые дочерний логгер добавляет к каждому логу
+#[derive(Debug, Clone)]
+pub struct LogContext {
+    pub component: Component,
+    pub fields: Vec<(String, FieldValue)>,
+}
+
+impl LogContext {
+    pub fn new(component: Component, fields: Vec<(String, FieldValue)>) -> Self {
+        Self { component, fields }
+    }
+
+    /// Лог с компонентом и полями контекста, поля лога идут после полей контекста
+    pub fn entry(
+        &self,
+        message: String,
+        status: Status,
+        fields: Vec<(String, FieldValue)>,
+        time: LogTime,
+    ) -> Log {
+        let mut log = Log::new(status, message, self.component.clone(), time);
+        log.fields = merge_fields(self.fields.clone(), fields);
+        log
+    }
+
+    /// Вложенный контекст: новый компонент, поля дополняют и переопределяют текущие
+    pub fn nested(&self, component: Component, fields: Vec<(String, FieldValue)>) -> Self {
+        Self::new(component, merge_fields(self.fields.clone(), fields))
+    }
+}
+
+/// Поля `extra` добавляются к `base`, совпадающие ключи заменяются
+fn merge_fields(
+    mut base: Vec<(String, FieldValue)>,
+    extra: Vec<(String, FieldValue)>,
+) -> Vec<(String, FieldValue)> {
+    for (key, value) in extra {
+        match base.iter_mut().find(|(base_key, _)| *base_key == key) {
+            Some((_, base_value)) => *base_value = value,
+            None => base.push((key, value)),
+        }
+    }
+    base
+}
+
+/// Дочерний логгер, пишущий в родительский `Logger` с общим компонентом и полями
+pub struct ChildLogger<'a> {
+    logger: &'a mut Logger,
+    pub context: LogContext,
+}
+
+impl Logger {
+    /// Дочерний логгер с фиксированным компонентом и полями
+    pub fn child(&mut self, component: Component, fields: Vec<(String, FieldValue)>) -> ChildLogger<'_> {
+        ChildLogger {
+            logger: self,
+            context: LogContext::new(component, fields),
+        }
+    }
+}
+
+impl ChildLogger<'_> {
+    /// Добавление лога с компонентом и полями дочернего логгера
+    pub fn add_log(&mut self, message: String, status: Status) -> Option<String> {
+        self.add_log_with_fields(message, status, Vec::new())
+    }
+
+    /// Добавление лога с дополнительными полями
+    pub fn add_log_with_fields(
+        &mut self,
+        message: String,
+        status: Status,
+        fields: Vec<(String, FieldValue)>,
+    ) -> Option<String> {
+        let log = self.context.entry(message, status, fields, self.logger.now());
+        self.logger.add_entry(log)
+    }
+
+    /// Вложенный дочерний логгер, наследующий поля
+    pub fn child(&mut self, component: Component, fields: Vec<(String, FieldValue)>) -> ChildLogger<'_> {
+        ChildLogger {
+            context: self.context.nested(component, fields),
+            logger: self.logger,
+        }
+    }
+
+    /// Родительский логгер
+    pub fn logger(&mut self) -> &mut Logger {
+        self.logger
+    }
+}
+
+/// Дочерний логгер для `SharedLogger`, можно клонировать и передавать в другие потоки
+#[derive(Clone)]
+pub struct SharedChildLogger {
+    logger: SharedLogger,
+    pub context: LogContext,
+}
+
+impl SharedLogger {
+    /// Дочерний логгер с фиксированным компонентом и полями
+    pub fn child(&self, component: Component, fields: Vec<(String, FieldValue)>) -> SharedChildLogger {
+        SharedChildLogger {
+            logger: self.clone(),
+            context: LogContext::new(component, fields),
+        }
+    }
+}
+
+impl SharedChildLogger {
+    /// Добавление лога с компонентом и полями дочернего логгера
+    pub fn add_log(&self, message: String, status: Status) -> Option<String> {
+        self.add_log_with_fields(message, status, Vec::new())
+    }
+
+    /// Добавление лога с дополнительными полями
+    pub fn add_log_with_fields(
+        &self,
+        message: String,
+        status: Status,
+        fields: Vec<(String, FieldValue)>,
+    ) -> Option<String> {
+        let mut logger = self.logger.lock();
+        let log = self.context.entry(message, status, fields, logger.now());
+        logger.add_entry(log)
+    }
+
+    /// Вложенный дочерний логгер, наследующий поля
+    pub fn child(&self, component: Component, fields: Vec<(String, FieldValue)>) -> SharedChildLogger {
+        SharedChildLogger {
+            logger: self.logger.clone(),
+            context: self.context.nested(component, fields),
+        }
+    }
+
+    /// Родительский логгер
+    pub fn logger(&self) -> &SharedLogger {
+        &self.logger
+    }
+}
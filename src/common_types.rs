@@ -9,16 +9,100 @@
  | CopyRight: © 2025 CodeLibraty Foundation
  */
 
+use std::cmp::Ordering;
+use std::collections::HashSet;
 use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
 
 /// Статусы, показывают результат выполнения
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Статусы упорядочены по важности:
+/// `Trace < Debug < Info < Ok < Warn < Error < Fatal`.
+/// Пользовательский статус `Custom` имеет важность `Info`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Status {
     Ok,
     Error,
     Fatal,
     Info,
     Warn,
+    Trace,
+    Debug,
+    Custom(&'static str),
+}
+
+impl Status {
+    /// Уровень важности статуса
+    pub fn severity(&self) -> u8 {
+        match self {
+            Status::Trace => 0,
+            Status::Debug => 1,
+            Status::Info | Status::Custom(_) => 2,
+            Status::Ok => 3,
+            Status::Warn => 4,
+            Status::Error => 5,
+            Status::Fatal => 6,
+        }
+    }
+
+    /// Статус по имени: встроенный, если имя известно, иначе пользовательский
+    ///
+    /// Имя пользовательского статуса — слово до 32 символов из букв, цифр, `-` и `_`,
+    /// начинающееся с буквы. Имена хранятся до конца работы программы, каждое
+    /// выделяется один раз, всего не больше `MAX_CUSTOM_STATUSES`; остальные
+    /// имена — ошибка.
+    pub fn from_name(name: &str) -> Result<Self, ParseStatusError> {
+        if let Ok(status) = name.parse() {
+            return Ok(status);
+        }
+
+        let unknown = || ParseStatusError(name.to_string());
+        let mut chars = name.chars();
+        let valid = chars.next().is_some_and(char::is_alphabetic)
+            && chars.all(|ch| ch.is_alphanumeric() || ch == '-' || ch == '_')
+            && name.chars().count() <= 32;
+        if !valid {
+            return Err(unknown());
+        }
+
+        static NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+        let mut names = NAMES
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        match names.get(name) {
+            Some(interned) => Ok(Status::Custom(interned)),
+            None if names.len() >= MAX_CUSTOM_STATUSES => Err(unknown()),
+            None => {
+                let interned: &'static str = Box::leak(name.to_string().into_boxed_str());
+                names.insert(interned);
+                Ok(Status::Custom(interned))
+            }
+        }
+    }
+
+    /// Порядок статуса среди статусов с одинаковой важностью
+    fn rank(&self) -> (u8, u8, &'static str) {
+        match self {
+            Status::Custom(name) => (self.severity(), 1, name),
+            _ => (self.severity(), 0, ""),
+        }
+    }
+}
+
+impl PartialOrd for Status {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Status {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank().cmp(&other.rank())
+    }
 }
 
 impl fmt::Display for Status {
@@ -29,7 +113,136 @@ impl fmt::Display for Status {
             Status::Fatal => "Fatal",
             Status::Info => "Info",
             Status::Warn => "Warning",
+            Status::Trace => "Trace",
+            Status::Debug => "Debug",
+            Status::Custom(name) => name,
         };
         write!(f, "{}", status_str)
     }
-}
\ No newline at end of file
+}
+
+/// Сколько разных пользовательских статусов может создать `Status::from_name`
+pub const MAX_CUSTOM_STATUSES: usize = 64;
+
+/// Ошибка разбора статуса из строки
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseStatusError(pub String);
+
+impl fmt::Display for ParseStatusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown status '{}'", self.0)
+    }
+}
+
+impl std::error::Error for ParseStatusError {}
+
+impl FromStr for Status {
+    type Err = ParseStatusError;
+
+    /// Разбор встроенного статуса без учёта регистра (`warn`, `Warning`, `ERROR`, ...)
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.trim().to_lowercase().as_str() {
+            "ok" => Ok(Status::Ok),
+            "error" => Ok(Status::Error),
+            "fatal" => Ok(Status::Fatal),
+            "info" => Ok(Status::Info),
+            "warn" | "warning" => Ok(Status::Warn),
+            "trace" => Ok(Status::Trace),
+            "debug" => Ok(Status::Debug),
+            _ => Err(ParseStatusError(text.to_string())),
+        }
+    }
+}
+
+/// Значение структурированного поля лога
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Str(String),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Bool(bool),
+}
+
+impl fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldValue::Str(value) => write!(f, "{}", value),
+            FieldValue::Int(value) => write!(f, "{}", value),
+            FieldValue::UInt(value) => write!(f, "{}", value),
+            FieldValue::Float(value) => write!(f, "{}", value),
+            FieldValue::Bool(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl From<&str> for FieldValue {
+    fn from(value: &str) -> Self {
+        FieldValue::Str(value.to_string())
+    }
+}
+
+impl From<String> for FieldValue {
+    fn from(value: String) -> Self {
+        FieldValue::Str(value)
+    }
+}
+
+impl From<&String> for FieldValue {
+    fn from(value: &String) -> Self {
+        FieldValue::Str(value.clone())
+    }
+}
+
+impl From<&Path> for FieldValue {
+    fn from(value: &Path) -> Self {
+        FieldValue::Str(value.display().to_string())
+    }
+}
+
+impl From<PathBuf> for FieldValue {
+    fn from(value: PathBuf) -> Self {
+        FieldValue::Str(value.display().to_string())
+    }
+}
+
+impl From<bool> for FieldValue {
+    fn from(value: bool) -> Self {
+        FieldValue::Bool(value)
+    }
+}
+
+impl From<f32> for FieldValue {
+    fn from(value: f32) -> Self {
+        FieldValue::Float(value as f64)
+    }
+}
+
+impl From<f64> for FieldValue {
+    fn from(value: f64) -> Self {
+        FieldValue::Float(value)
+    }
+}
+
+macro_rules! field_value_from_int {
+    ($variant:ident, $target:ty, $($source:ty),*) => {
+        $(
+            impl From<$source> for FieldValue {
+                fn from(value: $source) -> Self {
+                    FieldValue::$variant(value as $target)
+                }
+            }
+        )*
+    };
+}
+
+field_value_from_int!(Int, i64, i8, i16, i32, i64, isize);
+field_value_from_int!(UInt, u64, u8, u16, u32, u64, usize);
+
+/// Макрос для создания списка полей лога: `fields!("file" => path, "duration_ms" => 42)`
+#[macro_export]
+macro_rules! fields {
+    ($($key:expr => $value:expr),* $(,)?) => {
+        vec![$(($key.to_string(), $crate::common_types::FieldValue::from($value))),*]
+    };
+}
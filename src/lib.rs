@@ -9,16 +9,54 @@
  | CopyRight: © 2025 CodeLibraty Foundation
  */
 
+pub mod async_sink;
+pub mod child_logger;
 pub mod colors;
 pub mod common_types;
+pub mod log_capture;
+pub mod log_clock;
+pub mod log_error;
+pub mod log_file;
+pub mod log_filter;
+pub mod log_formats;
+pub mod log_merge;
+pub mod log_parse;
+pub mod log_query;
+pub mod log_sink;
+pub mod log_span;
+pub mod log_summary;
+#[cfg(unix)]
+pub mod log_syslog;
+pub mod log_template;
 pub mod logger;
+pub mod panic_hook;
+pub mod shared_logger;
 pub mod stylish;
 pub mod theme_config;
 pub mod tree_printer;
 
+pub use async_sink::*;
+pub use child_logger::*;
 pub use colors::*;
 pub use common_types::*;
+pub use log_capture::*;
+pub use log_clock::*;
+pub use log_error::*;
+pub use log_file::*;
+pub use log_filter::*;
+pub use log_formats::*;
+pub use log_merge::*;
+pub use log_parse::*;
+pub use log_query::*;
+pub use log_sink::*;
+pub use log_span::*;
+pub use log_summary::*;
+#[cfg(unix)]
+pub use log_syslog::*;
+pub use log_template::*;
 pub use logger::*;
+pub use panic_hook::*;
+pub use shared_logger::*;
 pub use stylish::*;
 pub use theme_config::*;
 pub use tree_printer::*;
\ No newline at end of file
@@ -0,0 +1,183 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/log_capture.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::common_types::Status;
+use crate::log_clock::{ManualClock, SystemClock};
+use crate::log_sink::LogSink;
+use crate::logger::{Log, LogTime, Logger};
+
+/// Приёмник для тестов, собирающий логи в общий буфер
+///
+/// Клоны разделяют один буфер: один клон отдаётся логгеру, другой остаётся в тесте.
+#[derive(Clone, Default)]
+pub struct LogCapture {
+    entries: Arc<Mutex<Vec<(Log, String)>>>,
+}
+
+impl LogCapture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn entries(&self) -> MutexGuard<'_, Vec<(Log, String)>> {
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Все пойманные логи
+    pub fn logs(&self) -> Vec<Log> {
+        self.entries().iter().map(|(log, _)| log.clone()).collect()
+    }
+
+    /// Строки логов в стиле логгера, без цветов
+    pub fn lines(&self) -> Vec<String> {
+        self.entries().iter().map(|(_, line)| line.clone()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries().is_empty()
+    }
+
+    pub fn clear(&self) {
+        self.entries().clear();
+    }
+
+    /// Число логов с этим статусом (если задан), сообщение которых содержит текст (если задан)
+    pub fn count(&self, status: Option<Status>, contains: Option<&str>) -> usize {
+        self.entries()
+            .iter()
+            .filter(|(log, _)| matches(log, status, contains))
+            .count()
+    }
+
+    /// Паника, если подходящего лога нет
+    #[track_caller]
+    pub fn assert_logged(&self, status: Option<Status>, contains: Option<&str>) {
+        if self.count(status, contains) == 0 {
+            panic!(
+                "expected a log{}, captured:\n{}",
+                describe(status, contains),
+                self.dump()
+            );
+        }
+    }
+
+    /// Паника, если подходящий лог есть
+    #[track_caller]
+    pub fn assert_not_logged(&self, status: Option<Status>, contains: Option<&str>) {
+        if self.count(status, contains) > 0 {
+            panic!(
+                "expected no log{}, captured:\n{}",
+                describe(status, contains),
+                self.dump()
+            );
+        }
+    }
+
+    /// Пойманные строки для сообщений об ошибках
+    fn dump(&self) -> String {
+        let lines = self.lines();
+        if lines.is_empty() {
+            return "    (nothing)".to_string();
+        }
+        lines
+            .iter()
+            .map(|line| format!("    {}", line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl LogSink for LogCapture {
+    fn write_log(&mut self, log: &Log, line: &str) -> io::Result<()> {
+        self.entries().push((log.clone(), line.to_string()));
+        Ok(())
+    }
+}
+
+impl Logger {
+    /// Подключение приёмника `LogCapture`, возвращает его клон для проверок
+    pub fn capture(&mut self) -> LogCapture {
+        let capture = LogCapture::new();
+        self.add_sink(capture.clone());
+        capture
+    }
+
+    /// Заморозка времени логгера, возвращает `ManualClock` для его перевода
+    ///
+    /// Логи без явного времени, этапы и `finish` получают замороженное время.
+    /// Оно хранится в логгере, а не в потоке, поэтому действует и для логов,
+    /// добавленных из других потоков через `SharedLogger` и дочерние логгеры.
+    pub fn freeze_time(&mut self, time: LogTime) -> ManualClock {
+        let clock = ManualClock::new(time);
+        self.set_clock(clock.clone());
+        clock
+    }
+
+    /// Возврат к системным часам
+    pub fn unfreeze_time(&mut self) {
+        self.set_clock(SystemClock);
+    }
+}
+
+fn matches(log: &Log, status: Option<Status>, contains: Option<&str>) -> bool {
+    status.is_none_or(|status| log.status == status)
+        && contains.is_none_or(|text| log.message.contains(text))
+}
+
+fn describe(status: Option<Status>, contains: Option<&str>) -> String {
+    let mut result = String::new();
+    if let Some(status) = status {
+        result.push_str(&format!(" with status {}", status));
+    }
+    if let Some(text) = contains {
+        result.push_str(&format!(" containing {:?}", text));
+    }
+    result
+}
+
+/// Проверка, что `LogCapture` поймал подходящий лог
+///
+/// `assert_logged!(capture, Status::Warn)`,
+/// `assert_logged!(capture, contains "deprecated")`,
+/// `assert_logged!(capture, Status::Warn, contains "deprecated")`.
+#[macro_export]
+macro_rules! assert_logged {
+    ($capture:expr, contains $text:expr $(,)?) => {
+        $capture.assert_logged(None, Some($text))
+    };
+    ($capture:expr, $status:expr, contains $text:expr $(,)?) => {
+        $capture.assert_logged(Some($status), Some($text))
+    };
+    ($capture:expr, $status:expr $(,)?) => {
+        $capture.assert_logged(Some($status), None)
+    };
+}
+
+/// Проверка, что `LogCapture` не поймал подходящих логов, аргументы как у `assert_logged!`
+#[macro_export]
+macro_rules! assert_not_logged {
+    ($capture:expr, contains $text:expr $(,)?) => {
+        $capture.assert_not_logged(None, Some($text))
+    };
+    ($capture:expr, $status:expr, contains $text:expr $(,)?) => {
+        $capture.assert_not_logged(Some($status), Some($text))
+    };
+    ($capture:expr, $status:expr $(,)?) => {
+        $capture.assert_not_logged(Some($status), None)
+    };
+}
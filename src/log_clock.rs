@@ -0,0 +1,90 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/log_clock.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+use chrono::TimeDelta;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::logger::{LogTime, Logger};
+
+/// Источник текущего времени для логгера
+pub trait Clock: Send {
+    fn now(&self) -> LogTime;
+}
+
+/// Системные часы
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> LogTime {
+        LogTime::now()
+    }
+}
+
+/// Часы, всегда показывающие одно и то же время
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub LogTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> LogTime {
+        self.0
+    }
+}
+
+/// Часы, которые переводятся вручную
+///
+/// Клоны показывают одно время: один клон отдаётся логгеру, другим управляет тест
+/// или симуляция.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    time: Arc<Mutex<LogTime>>,
+}
+
+impl ManualClock {
+    pub fn new(start: LogTime) -> Self {
+        Self {
+            time: Arc::new(Mutex::new(start)),
+        }
+    }
+
+    fn time(&self) -> MutexGuard<'_, LogTime> {
+        self.time.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Перевод часов на указанное время
+    pub fn set(&self, time: LogTime) {
+        *self.time() = time;
+    }
+
+    /// Сдвиг часов вперёд (или назад при отрицательном `delta`)
+    pub fn advance(&self, delta: TimeDelta) {
+        let mut time = self.time();
+        *time = LogTime::from_datetime(time.datetime + delta);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> LogTime {
+        *self.time()
+    }
+}
+
+impl Logger {
+    /// Текущее время по часам логгера в его часовом поясе
+    pub fn now(&self) -> LogTime {
+        self.time_zone.apply(self.clock.now())
+    }
+
+    /// Замена часов логгера
+    pub fn set_clock(&mut self, clock: impl Clock + 'static) {
+        self.clock = Box::new(clock);
+    }
+}
@@ -0,0 +1,137 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/log_error.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+use std::backtrace::Backtrace;
+use std::error::Error;
+
+use crate::common_types::{FieldValue, Status};
+use crate::logger::{Component, Log, LogTime, Logger};
+use crate::shared_logger::SharedLogger;
+use crate::tree_printer::BranchStyle;
+
+/// Как записывать ошибку в лог
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorReport {
+    pub status: Status,
+    /// Ветки `├─`/`╰─` или просто отступы перед причинами
+    pub branch_style: BranchStyle,
+    /// Добавлять стек вызовов в поле `backtrace`
+    ///
+    /// На стабильном Rust стек ошибки недоступен, поэтому записывается
+    /// стек места, где ошибка попала в лог.
+    pub backtrace: bool,
+}
+
+impl Default for ErrorReport {
+    fn default() -> Self {
+        Self {
+            status: Status::Error,
+            branch_style: BranchStyle::Unicode,
+            backtrace: false,
+        }
+    }
+}
+
+impl ErrorReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn status(mut self, status: Status) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn branch_style(mut self, style: BranchStyle) -> Self {
+        self.branch_style = style;
+        self
+    }
+
+    pub fn backtrace(mut self, enabled: bool) -> Self {
+        self.backtrace = enabled;
+        self
+    }
+
+    /// Лог с ошибкой и всей цепочкой её причин на момент `time`
+    pub fn to_log(&self, error: &dyn Error, component: Component, time: LogTime) -> Log {
+        let message = format_error_chain(error, self.branch_style);
+        let mut log = Log::new(self.status, message, component, time);
+        if self.backtrace {
+            log.fields.push((
+                "backtrace".to_string(),
+                FieldValue::Str(Backtrace::force_capture().to_string()),
+            ));
+        }
+        log
+    }
+}
+
+/// Ошибка и её причины из `source()`, каждая причина на своей строке
+///
+/// ```text
+/// failed to build project
+///   ├─ caused by: failed to parse src/main.rs
+///   ╰─ caused by: unexpected token '}'
+/// ```
+pub fn format_error_chain(error: &dyn Error, style: BranchStyle) -> String {
+    let mut causes = Vec::new();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        causes.push(cause.to_string());
+        source = cause.source();
+    }
+
+    let mut result = error.to_string();
+    for (index, cause) in causes.iter().enumerate() {
+        let glyph = match style {
+            BranchStyle::Unicode if index + 1 < causes.len() => "├─ ",
+            BranchStyle::Unicode => "╰─ ",
+            BranchStyle::Indent => "",
+        };
+        result.push_str(&format!("\n  {}caused by: {}", glyph, cause));
+    }
+    result
+}
+
+impl Logger {
+    /// Запись ошибки со всей цепочкой причин как `Status::Error`
+    pub fn log_error(&mut self, error: &dyn Error, component: Component) -> Option<String> {
+        self.log_error_with(error, component, &ErrorReport::default())
+    }
+
+    /// Запись ошибки с настройками
+    pub fn log_error_with(
+        &mut self,
+        error: &dyn Error,
+        component: Component,
+        report: &ErrorReport,
+    ) -> Option<String> {
+        let log = report.to_log(error, component, self.now());
+        self.add_entry(log)
+    }
+}
+
+impl SharedLogger {
+    /// Запись ошибки со всей цепочкой причин, см. `Logger::log_error`
+    pub fn log_error(&self, error: &dyn Error, component: Component) -> Option<String> {
+        self.lock().log_error(error, component)
+    }
+
+    /// Запись ошибки с настройками, см. `Logger::log_error_with`
+    pub fn log_error_with(
+        &self,
+        error: &dyn Error,
+        component: Component,
+        report: &ErrorReport,
+    ) -> Option<String> {
+        self.lock().log_error_with(error, component, report)
+    }
+}
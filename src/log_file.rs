@@ -0,0 +1,408 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/log_file.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+use chrono::{DateTime, Local};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::log_sink::LogSink;
+use crate::logger::{Log, LogTime};
+
+/// Как открывать файл логов
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileOpenPolicy {
+    /// Дописывать в конец файла, создавая его при необходимости
+    Append,
+    /// Перезаписывать файл
+    Truncate,
+    /// Создавать новый файл, ошибка если он уже существует
+    CreateNew,
+    /// Записывать во временный файл и атомарно заменять им целевой
+    Atomic,
+}
+
+/// Открытие файла логов по политике, родительские каталоги создаются автоматически
+///
+/// Для `Atomic` открывается временный файл рядом с целевым,
+/// его нужно переименовать через `fs::rename` после записи.
+pub fn open_log_file(path: &Path, policy: FileOpenPolicy) -> io::Result<File> {
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut options = OpenOptions::new();
+    match policy {
+        FileOpenPolicy::Append => options.create(true).append(true),
+        FileOpenPolicy::Truncate => options.create(true).write(true).truncate(true),
+        FileOpenPolicy::CreateNew => options.write(true).create_new(true),
+        FileOpenPolicy::Atomic => {
+            return options
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(temp_path(path))
+        }
+    };
+
+    options.open(path)
+}
+
+/// Запись строк в файл логов, каждая строка завершается переводом строки
+pub fn write_log_file(path: &Path, policy: FileOpenPolicy, lines: &[String]) -> io::Result<()> {
+    let mut file = open_log_file(path, policy)?;
+
+    let mut content = String::new();
+    for line in lines {
+        content.push_str(line);
+        content.push('\n');
+    }
+    if policy != FileOpenPolicy::Atomic {
+        return file.write_all(content.as_bytes());
+    }
+
+    let temp = temp_path(path);
+    let written = file.write_all(content.as_bytes()).and_then(|()| file.sync_all());
+    drop(file);
+
+    // Временный файл не должен оставаться после неудачной записи
+    let result = written.and_then(|()| fs::rename(&temp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+/// Временный файл для атомарной записи, лежит в том же каталоге что и целевой
+fn temp_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()))
+}
+
+/// Приёмник, построчно дописывающий логи в файл
+pub struct FileSink {
+    file: File,
+}
+
+impl FileSink {
+    /// Открытие файла, политика `Atomic` для потоковой записи не поддерживается
+    pub fn open(path: impl AsRef<Path>, policy: FileOpenPolicy) -> io::Result<Self> {
+        if policy == FileOpenPolicy::Atomic {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "atomic policy is not supported for streaming file output",
+            ));
+        }
+
+        Ok(Self {
+            file: open_log_file(path.as_ref(), policy)?,
+        })
+    }
+}
+
+impl LogSink for FileSink {
+    fn write_log(&mut self, _log: &Log, line: &str) -> io::Result<()> {
+        self.file.write_all(format!("{}\n", line).as_bytes())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Граница времени, на которой файл ротируется
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationInterval {
+    Hourly,
+    Daily,
+}
+
+impl RotationInterval {
+    fn label_format(&self) -> &'static str {
+        match self {
+            RotationInterval::Hourly => "%Y-%m-%d-%H",
+            RotationInterval::Daily => "%Y-%m-%d",
+        }
+    }
+}
+
+/// Имена ротированных файлов
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationNaming {
+    /// `build.log.1`, `build.log.2`, ... (1 — самый новый)
+    Numbered,
+    /// `build.log.2025-03-07`, `build.log.2025-03-07-14`, ...
+    Dated,
+}
+
+/// Правила ротации файла логов
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RotationPolicy {
+    /// Ротировать, когда файл превысит этот размер
+    pub max_bytes: Option<u64>,
+    /// Ротировать на границе часа/дня
+    pub interval: Option<RotationInterval>,
+    /// Сколько старых файлов хранить
+    pub keep: usize,
+    pub naming: RotationNaming,
+    /// Сжимать ротированные файлы в gzip (нужна фича `gzip`)
+    pub compress: bool,
+}
+
+impl Default for RotationPolicy {
+    fn default() -> Self {
+        Self {
+            max_bytes: None,
+            interval: None,
+            keep: 5,
+            naming: RotationNaming::Numbered,
+            compress: false,
+        }
+    }
+}
+
+/// Приёмник, пишущий в файл с ротацией по размеру и времени
+pub struct RotatingFileSink {
+    path: PathBuf,
+    policy: RotationPolicy,
+    file: File,
+    size: u64,
+    /// Метка периода, к которому относятся логи в текущем файле
+    period: Option<String>,
+}
+
+impl RotatingFileSink {
+    /// Открытие файла для дописывания с правилами ротации
+    pub fn open(path: impl AsRef<Path>, policy: RotationPolicy) -> io::Result<Self> {
+        if policy.compress && !cfg!(feature = "gzip") {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "compressing rotated logs requires the `gzip` feature",
+            ));
+        }
+
+        let path = path.as_ref().to_path_buf();
+        let file = open_log_file(&path, FileOpenPolicy::Append)?;
+        let metadata = file.metadata()?;
+
+        // Период существующего файла определяем по времени его изменения
+        let period = match (policy.interval, metadata.len()) {
+            (Some(interval), size) if size > 0 => metadata.modified().ok().map(|modified| {
+                let modified = LogTime::from_datetime(DateTime::<Local>::from(modified));
+                modified.format_with(interval.label_format()).to_string()
+            }),
+            _ => None,
+        };
+
+        Ok(Self {
+            path,
+            policy,
+            size: metadata.len(),
+            file,
+            period,
+        })
+    }
+
+    /// Запись строки, `time` определяет период для ротации по времени
+    pub fn write_line(&mut self, line: &str, time: &LogTime) -> io::Result<()> {
+        let len = line.len() as u64 + 1;
+        let period = self
+            .policy
+            .interval
+            .map(|interval| time.format_with(interval.label_format()).to_string());
+
+        let period_changed = period.is_some() && self.period.is_some() && period != self.period;
+        let too_big = self
+            .policy
+            .max_bytes
+            .is_some_and(|max_bytes| self.size > 0 && self.size + len > max_bytes);
+
+        if period_changed || too_big {
+            self.rotate(time)?;
+        }
+        if period.is_some() {
+            self.period = period;
+        }
+
+        self.file.write_all(format!("{}\n", line).as_bytes())?;
+        self.size += len;
+        Ok(())
+    }
+
+    /// Принудительная ротация текущего файла
+    pub fn rotate(&mut self, time: &LogTime) -> io::Result<()> {
+        self.file.flush()?;
+
+        if self.policy.keep == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            let rotated = match self.policy.naming {
+                RotationNaming::Numbered => {
+                    self.shift_numbered()?;
+                    self.sibling("1")
+                }
+                RotationNaming::Dated => {
+                    let label = self.period.clone().unwrap_or_else(|| {
+                        time.format_with("%Y-%m-%d-%H%M%S").to_string()
+                    });
+                    self.unique_dated(&label)
+                }
+            };
+
+            fs::rename(&self.path, &rotated)?;
+            if self.policy.compress {
+                compress_file(&rotated)?;
+            }
+            if self.policy.naming == RotationNaming::Dated {
+                self.prune_dated()?;
+            }
+        }
+
+        self.file = open_log_file(&self.path, FileOpenPolicy::Truncate)?;
+        self.size = 0;
+        self.period = None;
+        Ok(())
+    }
+
+    /// Путь `<файл>.<suffix>` рядом с текущим файлом
+    fn sibling(&self, suffix: &str) -> PathBuf {
+        let mut name = self.path.file_name().unwrap_or_default().to_os_string();
+        name.push(format!(".{}", suffix));
+        self.path.with_file_name(name)
+    }
+
+    /// Путь с `.gz`, если ротированные файлы сжимаются
+    fn compressed(&self, path: PathBuf) -> PathBuf {
+        if self.policy.compress {
+            let mut name = path.into_os_string();
+            name.push(".gz");
+            PathBuf::from(name)
+        } else {
+            path
+        }
+    }
+
+    /// Сдвиг `.1 -> .2 -> ...`, самый старый файл удаляется
+    fn shift_numbered(&self) -> io::Result<()> {
+        let keep = self.policy.keep;
+        let oldest = self.compressed(self.sibling(&keep.to_string()));
+        if oldest.exists() {
+            fs::remove_file(oldest)?;
+        }
+
+        for index in (1..keep).rev() {
+            let from = self.compressed(self.sibling(&index.to_string()));
+            if from.exists() {
+                fs::rename(from, self.compressed(self.sibling(&(index + 1).to_string())))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Путь с датой, не занятый другим файлом
+    fn unique_dated(&self, label: &str) -> PathBuf {
+        let mut candidate = self.sibling(label);
+        let mut counter = 1;
+
+        while candidate.exists() || self.compressed(candidate.clone()).exists() {
+            candidate = self.sibling(&format!("{}.{}", label, counter));
+            counter += 1;
+        }
+
+        candidate
+    }
+
+    /// Удаление самых старых файлов с датой сверх `keep`
+    ///
+    /// Учитываются только имена, которые создаёт ротация: посторонние
+    /// `build.log.bak` или `build.log.lock` не трогаются.
+    fn prune_dated(&self) -> io::Result<()> {
+        let parent = match self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            Some(parent) => parent.to_path_buf(),
+            None => PathBuf::from("."),
+        };
+        let prefix = format!("{}.", self.path.file_name().unwrap_or_default().to_string_lossy());
+
+        let mut rotated: Vec<(std::time::SystemTime, PathBuf)> = Vec::new();
+        for entry in fs::read_dir(parent)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.strip_prefix(&prefix).is_some_and(is_dated_suffix) {
+                rotated.push((entry.metadata()?.modified()?, entry.path()));
+            }
+        }
+
+        rotated.sort();
+        let excess = rotated.len().saturating_sub(self.policy.keep);
+        for (_, path) in rotated.into_iter().take(excess) {
+            fs::remove_file(path)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Суффикс ротированного файла с датой: `2025-03-07`, `2025-03-07-14` или
+/// `2025-03-07-143045`, с необязательным счётчиком `.1` и `.gz`
+fn is_dated_suffix(suffix: &str) -> bool {
+    let is_number = |text: &str| !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit());
+
+    let suffix = suffix.strip_suffix(".gz").unwrap_or(suffix);
+    let label = match suffix.rsplit_once('.') {
+        Some((label, counter)) if is_number(counter) => label,
+        _ => suffix,
+    };
+
+    let parts: Vec<&str> = label.split('-').collect();
+    let lengths: Vec<usize> = parts.iter().map(|part| part.len()).collect();
+    parts.iter().all(|part| is_number(part))
+        && matches!(lengths.as_slice(), [4, 2, 2] | [4, 2, 2, 2] | [4, 2, 2, 6])
+}
+
+impl LogSink for RotatingFileSink {
+    fn write_log(&mut self, log: &Log, line: &str) -> io::Result<()> {
+        self.write_line(line, &log.time)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Сжатие файла в `<файл>.gz` с удалением исходного
+#[cfg(feature = "gzip")]
+fn compress_file(path: &Path) -> io::Result<()> {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".gz");
+
+    let mut source = File::open(path)?;
+    let mut encoder = GzEncoder::new(File::create(PathBuf::from(name))?, Compression::default());
+    io::copy(&mut source, &mut encoder)?;
+    encoder.finish()?.sync_all()?;
+
+    fs::remove_file(path)
+}
+
+#[cfg(not(feature = "gzip"))]
+fn compress_file(_path: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "compressing rotated logs requires the `gzip` feature",
+    ))
+}
@@ -0,0 +1,175 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/log_filter.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::common_types::Status;
+use crate::logger::Component;
+
+/// Переменная окружения с фильтром по умолчанию
+pub const DEFAULT_FILTER_ENV: &str = "PRETTYTERM_LOG";
+
+/// Одна директива фильтра: `target=status` или просто `status`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterDirective {
+    /// Сегменты цели (`codegen::emit` -> `["codegen", "emit"]`), пусто для уровня по умолчанию
+    pub target: Vec<String>,
+    pub status: Status,
+}
+
+impl FilterDirective {
+    /// Подходит ли директива к компоненту
+    ///
+    /// Сегменты цели должны идти подряд в пути компонента:
+    /// каталоги из `dir_path`, имя файла без расширения и имя функции.
+    fn matches(&self, component: &Component) -> bool {
+        if self.target.is_empty() {
+            return true;
+        }
+
+        // Имя файла можно указывать как с расширением, так и без
+        let file_stem = file_stem(&component.file_name);
+        let segment_matches = |segment: &String, target: &String| {
+            segment == target || (segment == file_stem && *target == component.file_name)
+        };
+
+        component_path(component)
+            .windows(self.target.len())
+            .any(|window| window.iter().zip(&self.target).all(|(s, t)| segment_matches(s, t)))
+    }
+}
+
+/// Ошибка разбора фильтра
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterParseError {
+    /// Неизвестный статус в директиве
+    InvalidStatus(String),
+    /// Пустая цель перед `=`
+    EmptyTarget(String),
+}
+
+impl fmt::Display for FilterParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterParseError::InvalidStatus(directive) => {
+                write!(f, "invalid status in filter directive '{}'", directive)
+            }
+            FilterParseError::EmptyTarget(directive) => {
+                write!(f, "empty target in filter directive '{}'", directive)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FilterParseError {}
+
+/// Фильтр логов по компонентам в стиле `RUST_LOG`
+///
+/// Пример: `warn,parser=debug,codegen::emit=trace`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogFilter {
+    pub directives: Vec<FilterDirective>,
+}
+
+impl LogFilter {
+    /// Разбор строки фильтра
+    pub fn parse(spec: &str) -> Result<Self, FilterParseError> {
+        let mut directives = Vec::new();
+
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let (target, status) = match directive.split_once('=') {
+                Some((target, status)) => {
+                    let target = target.trim();
+                    if target.is_empty() {
+                        return Err(FilterParseError::EmptyTarget(directive.to_string()));
+                    }
+                    let status = status
+                        .parse()
+                        .map_err(|_| FilterParseError::InvalidStatus(directive.to_string()))?;
+                    (split_target(target), status)
+                }
+                // Одиночное слово — либо уровень по умолчанию, либо цель со всеми логами
+                None => match directive.parse() {
+                    Ok(status) => (Vec::new(), status),
+                    Err(_) => (split_target(directive), Status::Trace),
+                },
+            };
+
+            directives.push(FilterDirective { target, status });
+        }
+
+        Ok(Self { directives })
+    }
+
+    /// Чтение фильтра из переменной окружения, `None` если она не задана
+    pub fn from_env(var: &str) -> Result<Option<Self>, FilterParseError> {
+        match std::env::var(var) {
+            Ok(spec) => Self::parse(&spec).map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Чтение фильтра из `PRETTYTERM_LOG`
+    pub fn from_default_env() -> Result<Option<Self>, FilterParseError> {
+        Self::from_env(DEFAULT_FILTER_ENV)
+    }
+
+    /// Минимальный статус для компонента, `None` если ни одна директива не подошла
+    ///
+    /// Побеждает самая длинная подходящая цель, при равенстве — последняя.
+    pub fn status_for(&self, component: &Component) -> Option<Status> {
+        self.directives
+            .iter()
+            .enumerate()
+            .filter(|(_, directive)| directive.matches(component))
+            .max_by_key(|(index, directive)| (directive.target.len(), *index))
+            .map(|(_, directive)| directive.status)
+    }
+}
+
+impl FromStr for LogFilter {
+    type Err = FilterParseError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        Self::parse(spec)
+    }
+}
+
+/// Разбиение цели на сегменты по `::` и `/`
+fn split_target(target: &str) -> Vec<String> {
+    target
+        .split("::")
+        .flat_map(|part| part.split(['/', '\\']))
+        .filter(|segment| !segment.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Путь компонента: каталоги, имя файла без расширения и функция
+fn component_path(component: &Component) -> Vec<String> {
+    let mut path: Vec<String> = split_target(&component.dir_path)
+        .into_iter()
+        .filter(|segment| segment != ".")
+        .collect();
+    path.push(file_stem(&component.file_name).to_string());
+    if !component.func_name.is_empty() {
+        path.push(component.func_name.clone());
+    }
+    path
+}
+
+fn file_stem(file_name: &str) -> &str {
+    match file_name.rsplit_once('.') {
+        Some((stem, _)) if !stem.is_empty() => stem,
+        _ => file_name,
+    }
+}
@@ -0,0 +1,189 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/log_formats.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+use std::fmt::Write;
+
+use crate::common_types::FieldValue;
+use crate::logger::Log;
+
+/// Экранирование строки для JSON (без окружающих кавычек)
+pub fn escape_json(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match ch {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            '\u{08}' => result.push_str("\\b"),
+            '\u{0C}' => result.push_str("\\f"),
+            ch if (ch as u32) < 0x20 => {
+                let _ = write!(result, "\\u{:04x}", ch as u32);
+            }
+            ch => result.push(ch),
+        }
+    }
+
+    result
+}
+
+/// Значение logfmt, в кавычках если это необходимо
+pub fn quote_logfmt(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value
+            .chars()
+            .any(|ch| ch == ' ' || ch == '=' || ch == '"' || ch == '\\' || ch.is_control());
+
+    if needs_quotes {
+        format!("\"{}\"", escape_json(value))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Значение поля как значение JSON
+pub fn field_to_json(value: &FieldValue) -> String {
+    match value {
+        FieldValue::Str(value) => format!("\"{}\"", escape_json(value)),
+        FieldValue::Int(value) => value.to_string(),
+        FieldValue::UInt(value) => value.to_string(),
+        // В JSON нет NaN и бесконечностей
+        FieldValue::Float(value) if !value.is_finite() => "null".to_string(),
+        // `{:?}` сохраняет дробную часть: 42.0 вместо 42
+        FieldValue::Float(value) => format!("{:?}", value),
+        FieldValue::Bool(value) => value.to_string(),
+    }
+}
+
+/// Встроенные ключи строки logfmt
+pub const LOGFMT_RESERVED_KEYS: &[&str] = &[
+    "time", "status", "msg", "file", "func", "dir", "line", "thread", "seq", "repeated",
+];
+
+/// Префикс полей, чьи имена совпадают со встроенными ключами logfmt: `field.time=...`
+pub const LOGFMT_FIELD_PREFIX: &str = "field.";
+
+/// Ключ logfmt: пробелы, `=`, `"` и управляющие символы заменяются на `_`
+pub fn logfmt_key(key: &str) -> String {
+    let key: String = key
+        .chars()
+        .map(|ch| match ch {
+            '=' | '"' => '_',
+            ch if ch.is_whitespace() || ch.is_control() => '_',
+            ch => ch,
+        })
+        .collect();
+
+    if key.is_empty() {
+        "_".to_string()
+    } else {
+        key
+    }
+}
+
+/// Поля лога в виде `key=value key2="value 2"`
+pub fn fields_to_logfmt(fields: &[(String, FieldValue)]) -> String {
+    fields
+        .iter()
+        .map(|(key, value)| format!("{}={}", logfmt_key(key), quote_logfmt(&value.to_string())))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Ключ поля в строке logfmt, встроенные ключи и сам префикс получают префикс `field.`
+pub(crate) fn logfmt_field_key(key: &str) -> String {
+    let key = logfmt_key(key);
+    if LOGFMT_RESERVED_KEYS.contains(&key.as_str()) || key.starts_with(LOGFMT_FIELD_PREFIX) {
+        format!("{}{}", LOGFMT_FIELD_PREFIX, key)
+    } else {
+        key
+    }
+}
+
+/// Сериализация лога в одну строку JSON (JSON Lines)
+pub fn log_to_json(log: &Log) -> String {
+    let mut component = format!(
+        "\"file\":\"{}\",\"func\":\"{}\",\"dir\":\"{}\"",
+        escape_json(&log.component.file_name),
+        escape_json(&log.component.func_name),
+        escape_json(&log.component.dir_path)
+    );
+    if let Some(line) = log.component.line {
+        let _ = write!(component, ",\"line\":{}", line);
+    }
+
+    let mut result = format!(
+        "{{\"time\":\"{}\",\"status\":\"{}\",\"message\":\"{}\",\"component\":{{{}}}",
+        log.time.to_rfc3339(),
+        escape_json(&log.status.to_string()),
+        escape_json(&log.message),
+        component
+    );
+
+    if let Some(thread) = &log.thread {
+        let _ = write!(result, ",\"thread\":\"{}\"", escape_json(thread));
+    }
+    if let Some(sequence) = log.sequence {
+        let _ = write!(result, ",\"seq\":{}", sequence);
+    }
+    if log.repeated > 0 {
+        let _ = write!(result, ",\"repeated\":{}", log.repeated);
+    }
+    if !log.fields.is_empty() {
+        let fields: Vec<String> = log
+            .fields
+            .iter()
+            .map(|(key, value)| format!("\"{}\":{}", escape_json(key), field_to_json(value)))
+            .collect();
+        let _ = write!(result, ",\"fields\":{{{}}}", fields.join(","));
+    }
+
+    result.push('}');
+    result
+}
+
+/// Сериализация лога в одну строку logfmt
+pub fn log_to_logfmt(log: &Log) -> String {
+    let mut pairs = vec![
+        ("time", log.time.to_rfc3339()),
+        ("status", log.status.to_string()),
+        ("msg", log.message.clone()),
+        ("file", log.component.file_name.clone()),
+        ("func", log.component.func_name.clone()),
+        ("dir", log.component.dir_path.clone()),
+    ];
+    if let Some(line) = log.component.line {
+        pairs.push(("line", line.to_string()));
+    }
+    if let Some(thread) = &log.thread {
+        pairs.push(("thread", thread.clone()));
+    }
+    if let Some(sequence) = log.sequence {
+        pairs.push(("seq", sequence.to_string()));
+    }
+    if log.repeated > 0 {
+        pairs.push(("repeated", log.repeated.to_string()));
+    }
+
+    let mut result = pairs
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, quote_logfmt(value)))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    for (key, value) in &log.fields {
+        let _ = write!(result, " {}={}", logfmt_field_key(key), quote_logfmt(&value.to_string()));
+    }
+
+    result
+}
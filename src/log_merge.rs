@@ -0,0 +1,203 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/log_merge.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+use chrono::{DateTime, Local};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::common_types::FieldValue;
+use crate::log_file::{write_log_file, FileOpenPolicy};
+use crate::log_formats::LOGFMT_FIELD_PREFIX;
+use crate::log_parse::{LogParseError, LogParser};
+use crate::logger::{Log, LogTime, Logger, LoggerPrintStyle, TimeFormat};
+
+/// Логи одного источника, например файла одного рабочего процесса
+#[derive(Debug, Clone)]
+pub struct LogSource {
+    /// Имя источника, попадает в поле `source` каждого лога
+    pub name: String,
+    pub logs: Vec<Log>,
+    /// Записи, которые не удалось разобрать
+    pub errors: Vec<LogParseError>,
+}
+
+impl LogSource {
+    /// Источник из уже готовых логов
+    pub fn new(name: impl Into<String>, logs: Vec<Log>) -> Self {
+        Self {
+            name: name.into(),
+            logs,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Чтение файла логов, стиль определяется по первой строке
+    ///
+    /// Имя источника — имя файла, время без даты относится ко дню
+    /// последнего изменения файла.
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        Self::read_at(path, file_base_time(path)?)
+    }
+
+    /// Чтение файла логов с определением стиля, время без даты относится ко дню `base_time`
+    pub fn read_at(path: impl AsRef<Path>, base_time: LogTime) -> io::Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+
+        let parser = match LogParser::detect(&text) {
+            Some(parser) => parser,
+            None if text.trim().is_empty() => LogParser::new(LoggerPrintStyle::Tiny),
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("unknown log format in {}", path.display()),
+                ))
+            }
+        };
+        Ok(Self::parse(path, &text, &parser.with_base_time(base_time)))
+    }
+
+    /// Чтение файла логов заданным парсером
+    pub fn read_with(path: impl AsRef<Path>, parser: &LogParser) -> io::Result<Self> {
+        let path = path.as_ref();
+        Ok(Self::parse(path, &fs::read_to_string(path)?, parser))
+    }
+
+    fn parse(path: &Path, text: &str, parser: &LogParser) -> Self {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+
+        let mut source = Self::new(name, Vec::new());
+        for result in parser.parse_str(text) {
+            match result {
+                Ok(log) => source.logs.push(log),
+                Err(error) => source.errors.push(error),
+            }
+        }
+        source
+    }
+}
+
+/// Время последнего изменения файла, от него отсчитывается время без даты
+///
+/// Если система не сообщает время изменения — текущее время.
+pub fn file_base_time(path: impl AsRef<Path>) -> io::Result<LogTime> {
+    Ok(fs::metadata(path)?
+        .modified()
+        .map(|modified| LogTime::from_datetime(DateTime::<Local>::from(modified)))
+        .unwrap_or_else(|_| LogTime::now()))
+}
+
+/// Объединение логов нескольких источников в один поток по времени
+///
+/// Логи с одинаковым временем идут в порядке добавления источников,
+/// а внутри источника — в исходном порядке.
+#[derive(Debug, Clone)]
+pub struct LogMerger {
+    pub sources: Vec<LogSource>,
+    /// Поле с именем источника, `None` — не помечать логи
+    ///
+    /// Поле лога с таким же именем не теряется, а переименовывается в `field.<имя>`.
+    pub source_field: Option<String>,
+    /// Формат времени в текстовых стилях
+    pub time_format: TimeFormat,
+}
+
+impl Default for LogMerger {
+    fn default() -> Self {
+        Self {
+            sources: Vec::new(),
+            source_field: Some("source".to_string()),
+            time_format: TimeFormat::Precise,
+        }
+    }
+}
+
+impl LogMerger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Добавление источника
+    pub fn source(mut self, source: LogSource) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    /// Имя поля с источником лога
+    pub fn source_field(mut self, field: Option<&str>) -> Self {
+        self.source_field = field.map(str::to_string);
+        self
+    }
+
+    pub fn time_format(mut self, time_format: TimeFormat) -> Self {
+        self.time_format = time_format;
+        self
+    }
+
+    /// Ошибки разбора всех источников с их именами
+    pub fn errors(&self) -> impl Iterator<Item = (&str, &LogParseError)> {
+        self.sources
+            .iter()
+            .flat_map(|source| source.errors.iter().map(|error| (source.name.as_str(), error)))
+    }
+
+    /// Все логи, упорядоченные по времени и помеченные источником
+    pub fn merge(&self) -> Vec<Log> {
+        let mut entries: Vec<(LogTime, usize, usize, &Log)> = self
+            .sources
+            .iter()
+            .enumerate()
+            .flat_map(|(source_index, source)| {
+                source
+                    .logs
+                    .iter()
+                    .enumerate()
+                    .map(move |(position, log)| (log.time, source_index, position, log))
+            })
+            .collect();
+        entries.sort_by_key(|(time, source_index, position, _)| (*time, *source_index, *position));
+
+        entries
+            .into_iter()
+            .map(|(_, source_index, _, log)| {
+                let mut log = log.clone();
+                if let Some(field) = &self.source_field {
+                    // Собственное поле лога с тем же именем сохраняется как `field.<имя>`
+                    if let Some((key, _)) = log.fields.iter_mut().find(|(key, _)| key == field) {
+                        *key = format!("{}{}", LOGFMT_FIELD_PREFIX, field);
+                    }
+                    let name = FieldValue::from(&self.sources[source_index].name);
+                    log.fields.push((field.clone(), name));
+                }
+                log
+            })
+            .collect()
+    }
+
+    /// Объединённые логи строками в стиле `style`
+    pub fn render(&self, style: &LoggerPrintStyle) -> Vec<String> {
+        let mut logger = Logger::new(LogTime::now(), false);
+        logger.style = style.clone();
+        logger.time_format = self.time_format.clone();
+
+        self.merge().iter().map(|log| logger.format_log(log)).collect()
+    }
+
+    /// Запись объединённого лога в файл
+    pub fn write(&self, path: impl AsRef<Path>, style: &LoggerPrintStyle, policy: FileOpenPolicy) -> io::Result<()> {
+        write_log_file(path.as_ref(), policy, &self.render(style))
+    }
+}
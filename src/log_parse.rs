@@ -0,0 +1,764 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/log_parse.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+use chrono::{NaiveDateTime, NaiveTime, TimeDelta, TimeZone};
+use std::borrow::Cow;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::common_types::{FieldValue, Status};
+use crate::log_formats::LOGFMT_FIELD_PREFIX;
+use crate::logger::{Component, Log, LogTime, LoggerPrintStyle};
+
+/// Ошибка разбора лога, `line` — номер строки начиная с 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogParseError {
+    /// Строка не подходит под разметку стиля
+    Malformed { line: usize, reason: String },
+    /// Пустое или содержащее пробелы имя статуса
+    InvalidStatus { line: usize, status: String },
+    /// Время в неизвестном формате
+    InvalidTime { line: usize, time: String },
+    /// Стиль нельзя разобрать обратно (пользовательские шаблоны)
+    UnsupportedStyle,
+}
+
+impl LogParseError {
+    /// Номер строки, к которой относится ошибка
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            LogParseError::Malformed { line, .. }
+            | LogParseError::InvalidStatus { line, .. }
+            | LogParseError::InvalidTime { line, .. } => Some(*line),
+            LogParseError::UnsupportedStyle => None,
+        }
+    }
+
+    /// Та же ошибка с другим номером строки, например номером в файле
+    pub fn at_line(self, number: usize) -> Self {
+        match self {
+            LogParseError::Malformed { reason, .. } => LogParseError::Malformed { line: number, reason },
+            LogParseError::InvalidStatus { status, .. } => {
+                LogParseError::InvalidStatus { line: number, status }
+            }
+            LogParseError::InvalidTime { time, .. } => LogParseError::InvalidTime { line: number, time },
+            LogParseError::UnsupportedStyle => LogParseError::UnsupportedStyle,
+        }
+    }
+}
+
+impl fmt::Display for LogParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogParseError::Malformed { line, reason } => {
+                write!(f, "line {}: malformed log entry: {}", line, reason)
+            }
+            LogParseError::InvalidStatus { line, status } => {
+                write!(f, "line {}: invalid status '{}'", line, status)
+            }
+            LogParseError::InvalidTime { line, time } => {
+                write!(f, "line {}: invalid time '{}'", line, time)
+            }
+            LogParseError::UnsupportedStyle => {
+                write!(f, "custom templates cannot be parsed back into logs")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LogParseError {}
+
+fn malformed(reason: impl Into<String>) -> LogParseError {
+    LogParseError::Malformed {
+        line: 1,
+        reason: reason.into(),
+    }
+}
+
+/// Разбор строк, записанных логгером, обратно в `Log`
+///
+/// Текстовые стили не хранят дату, поток и номер лога: время без даты
+/// относится к дню `base_time`, а `+12.345s` отсчитывается от него.
+/// Сообщения Tiny/Flat с `=` записываются в кавычках, поэтому текст вида
+/// `key=value` не путается с полями. Строки с отступом продолжают
+/// предыдущую запись: это строки многострочного сообщения, например цепочки
+/// причин ошибки, а в `Full` ещё и поля.
+#[derive(Debug, Clone)]
+pub struct LogParser {
+    pub style: LoggerPrintStyle,
+    pub base_time: LogTime,
+}
+
+impl LogParser {
+    /// Парсер для стиля, время без даты относится к сегодняшнему дню
+    pub fn new(style: LoggerPrintStyle) -> Self {
+        Self {
+            style,
+            base_time: LogTime::now(),
+        }
+    }
+
+    /// Парсер для стиля, определённого по первой записи текста
+    pub fn detect(text: &str) -> Option<Self> {
+        let mut lines = text.lines().skip_while(|line| line.trim().is_empty());
+        let mut entry = lines.next()?.to_string();
+        for line in lines.take_while(|line| line.starts_with([' ', '\t']) && !line.trim().is_empty()) {
+            entry.push('\n');
+            entry.push_str(line);
+        }
+        detect_style(&entry).map(Self::new)
+    }
+
+    /// Указание дня и точки отсчёта для времени без даты
+    pub fn with_base_time(mut self, base_time: LogTime) -> Self {
+        self.base_time = base_time;
+        self
+    }
+
+    /// Продолжает ли строка предыдущую запись
+    pub fn is_continuation(&self, line: &str) -> bool {
+        matches!(
+            self.style,
+            LoggerPrintStyle::Tiny | LoggerPrintStyle::Flat | LoggerPrintStyle::Full
+        ) && line.starts_with([' ', '\t'])
+            && !line.trim().is_empty()
+    }
+
+    /// Разбор одной записи вместе со строками продолжения
+    pub fn parse_entry(&self, entry: &str) -> Result<Log, LogParseError> {
+        let entry = entry.trim_end_matches(['\n', '\r']);
+        match &self.style {
+            LoggerPrintStyle::Tiny => self.parse_tiny(entry),
+            LoggerPrintStyle::Flat => self.parse_flat(entry),
+            LoggerPrintStyle::Full => self.parse_full(entry),
+            LoggerPrintStyle::Json => self.parse_json(entry),
+            LoggerPrintStyle::Logfmt => self.parse_logfmt(entry),
+            LoggerPrintStyle::Custom(_) => Err(LogParseError::UnsupportedStyle),
+        }
+    }
+
+    /// Разбор текста целиком, пустые строки пропускаются
+    ///
+    /// Ошибка в одной записи не мешает разбору остальных.
+    pub fn parse_str(&self, text: &str) -> Vec<Result<Log, LogParseError>> {
+        let mut entries: Vec<(usize, String)> = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let continues = self.is_continuation(line);
+            match entries.last_mut() {
+                Some((_, entry)) if continues => {
+                    entry.push('\n');
+                    entry.push_str(line);
+                }
+                _ if line.trim().is_empty() => {}
+                _ => entries.push((index + 1, line.to_string())),
+            }
+        }
+
+        entries
+            .into_iter()
+            .map(|(number, entry)| self.parse_entry(&entry).map_err(|error| error.at_line(number)))
+            .collect()
+    }
+
+    /// Чтение и разбор файла логов
+    pub fn parse_file(&self, path: impl AsRef<Path>) -> io::Result<Vec<Result<Log, LogParseError>>> {
+        Ok(self.parse_str(&fs::read_to_string(path)?))
+    }
+
+    /// `Status: message fields | from file-func:func, time is time`
+    fn parse_tiny(&self, entry: &str) -> Result<Log, LogParseError> {
+        let (head, tail) = entry
+            .rsplit_once(" | from ")
+            .ok_or_else(|| malformed("expected ' | from <file>-func:<func>, time is <time>'"))?;
+        let (location, time) = tail
+            .rsplit_once(", time is ")
+            .ok_or_else(|| malformed("expected ', time is <time>'"))?;
+        let (file, func) = location
+            .rsplit_once("-func:")
+            .ok_or_else(|| malformed("expected '<file>-func:<func>'"))?;
+
+        let component = Component::new(file.to_string(), func.to_string(), String::new());
+        self.build_inline(head, component, time)
+    }
+
+    /// `Status: message fields | file file | time time`
+    fn parse_flat(&self, entry: &str) -> Result<Log, LogParseError> {
+        let (rest, time) = entry
+            .rsplit_once(" | time ")
+            .ok_or_else(|| malformed("expected ' | time <time>'"))?;
+        let (head, file) = rest
+            .rsplit_once(" | file ")
+            .ok_or_else(|| malformed("expected ' | file <file>'"))?;
+
+        let component = Component::new(file.to_string(), String::new(), String::new());
+        self.build_inline(head, component, time)
+    }
+
+    /// Общая часть Tiny/Flat: `Status: message key=value ...`
+    fn build_inline(&self, head: &str, component: Component, time: &str) -> Result<Log, LogParseError> {
+        let (status, message) = head
+            .split_once(": ")
+            .ok_or_else(|| malformed("expected '<status>: <message>'"))?;
+
+        // Сообщение с `=` записано в кавычках, поэтому первое `=` вне кавычек — начало полей
+        let (message, fields) = if message.starts_with('"') {
+            let (text, consumed) = json::parse_string(message).map_err(malformed)?;
+            let fields = match &message[consumed..] {
+                "" => Vec::new(),
+                rest => parse_logfmt_pairs(rest)
+                    .filter(|_| rest.starts_with(' '))
+                    .ok_or_else(|| malformed("expected 'key=value' fields after the quoted message"))?,
+            };
+            (Cow::Owned(text), fields)
+        } else {
+            let fields_start = message
+                .find('=')
+                .and_then(|equals| message[..equals].rfind(' '))
+                .and_then(|space| Some((space, parse_logfmt_pairs(&message[space + 1..])?)));
+            match fields_start {
+                Some((space, fields)) => (Cow::Borrowed(&message[..space]), fields),
+                None => (Cow::Borrowed(message), Vec::new()),
+            }
+        };
+
+        let mut log = Log::new(parse_status(status)?, String::new(), component, self.parse_time(time)?);
+        set_message(&mut log, &message);
+        log.fields = fields
+            .into_iter()
+            .map(|(key, value)| (key, parse_field_value(&value)))
+            .collect();
+        Ok(log)
+    }
+
+    /// `[Status|time][dir/file-func]: message`, строки сообщения и строки `    key = value`
+    fn parse_full(&self, entry: &str) -> Result<Log, LogParseError> {
+        let mut lines = entry.lines();
+        let first = lines.next().unwrap_or_default();
+
+        let rest = first
+            .strip_prefix('[')
+            .ok_or_else(|| malformed("expected '[<status>|<time>]'"))?;
+        let (status, rest) = rest
+            .split_once('|')
+            .ok_or_else(|| malformed("expected '[<status>|<time>]'"))?;
+        let (time, rest) = rest
+            .split_once("][")
+            .ok_or_else(|| malformed("expected '[<status>|<time>][<component>]'"))?;
+        let (location, message) = rest
+            .split_once("]: ")
+            .ok_or_else(|| malformed("expected '[<component>]: <message>'"))?;
+
+        let (dir, location) = location.rsplit_once('/').unwrap_or(("", location));
+        // Имя функции может содержать `-`, поэтому делим по первому `-` после расширения файла
+        let extension = location.find('.').unwrap_or(0);
+        let (file, func) = location[extension..]
+            .find('-')
+            .map(|dash| location.split_at(extension + dash))
+            .map(|(file, func)| (file, &func[1..]))
+            .ok_or_else(|| malformed("expected '<dir>/<file>-<func>'"))?;
+
+        let component = Component::new(file.to_string(), func.to_string(), dir.to_string());
+        let mut log = Log::new(parse_status(status)?, String::new(), component, self.parse_time(time)?);
+
+        // Строки сообщения идут с меньшим отступом, чем поля
+        let mut message = message.to_string();
+        let mut lines = lines.peekable();
+        while let Some(line) = lines.next_if(|line| !line.starts_with("    ")) {
+            message.push('\n');
+            message.push_str(line);
+        }
+        set_message(&mut log, &message);
+
+        for line in lines {
+            let (key, value) = line
+                .trim_start()
+                .split_once(" = ")
+                .ok_or_else(|| malformed(format!("expected '<key> = <value>', found '{}'", line.trim())))?;
+            log.fields.push((key.trim_end().to_string(), parse_field_value(value)));
+        }
+
+        Ok(log)
+    }
+
+    /// Строка `log_to_json`
+    fn parse_json(&self, entry: &str) -> Result<Log, LogParseError> {
+        let value = json::parse(entry).map_err(malformed)?;
+        let object = value.as_object().ok_or_else(|| malformed("expected a JSON object"))?;
+
+        let text = |key: &str| -> Result<String, LogParseError> {
+            json::get(object, key)
+                .and_then(json::Value::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| malformed(format!("missing string '{}'", key)))
+        };
+
+        let component = json::get(object, "component")
+            .and_then(json::Value::as_object)
+            .ok_or_else(|| malformed("missing object 'component'"))?;
+        let component_text = |key: &str| {
+            json::get(component, key)
+                .and_then(json::Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let mut parsed_component = Component::new(
+            component_text("file"),
+            component_text("func"),
+            component_text("dir"),
+        );
+        parsed_component.line = json::get(component, "line")
+            .and_then(json::Value::as_number)
+            .and_then(|line| line.parse().ok());
+
+        let mut log = Log::new(
+            parse_status(&text("status")?)?,
+            text("message")?,
+            parsed_component,
+            self.parse_time(&text("time")?)?,
+        );
+        log.thread = json::get(object, "thread")
+            .and_then(json::Value::as_str)
+            .map(str::to_string);
+        log.sequence = json::get(object, "seq")
+            .and_then(json::Value::as_number)
+            .and_then(|sequence| sequence.parse().ok());
+        log.repeated = json::get(object, "repeated")
+            .and_then(json::Value::as_number)
+            .and_then(|repeated| repeated.parse().ok())
+            .unwrap_or_default();
+
+        if let Some(fields) = json::get(object, "fields").and_then(json::Value::as_object) {
+            for (key, value) in fields {
+                let value = match value {
+                    json::Value::Str(text) => FieldValue::Str(text.clone()),
+                    json::Value::Number(number) => parse_field_value(number),
+                    json::Value::Bool(flag) => FieldValue::Bool(*flag),
+                    json::Value::Null => FieldValue::Float(f64::NAN),
+                    _ => return Err(malformed(format!("unsupported value of field '{}'", key))),
+                };
+                log.fields.push((key.clone(), value));
+            }
+        }
+
+        Ok(log)
+    }
+
+    /// Строка `log_to_logfmt`
+    fn parse_logfmt(&self, entry: &str) -> Result<Log, LogParseError> {
+        let pairs = parse_logfmt_pairs(entry).ok_or_else(|| malformed("expected 'key=value' pairs"))?;
+
+        let mut status = None;
+        let mut message = None;
+        let mut time = None;
+        let mut component = Component::new(String::new(), String::new(), String::new());
+        let mut thread = None;
+        let mut sequence = None;
+        let mut repeated = 0;
+        let mut fields = Vec::new();
+
+        for (key, value) in pairs {
+            match key.as_str() {
+                "time" => time = Some(value),
+                "status" => status = Some(value),
+                "msg" => message = Some(value),
+                "file" => component.file_name = value,
+                "func" => component.func_name = value,
+                "dir" => component.dir_path = value,
+                "line" => component.line = value.parse().ok(),
+                "thread" => thread = Some(value),
+                "seq" => sequence = value.parse().ok(),
+                "repeated" => repeated = value.parse().unwrap_or_default(),
+                _ => {
+                    let key = match key.strip_prefix(LOGFMT_FIELD_PREFIX) {
+                        Some(field) => field.to_string(),
+                        None => key,
+                    };
+                    fields.push((key, parse_field_value(&value)));
+                }
+            }
+        }
+
+        let status = status.ok_or_else(|| malformed("missing 'status'"))?;
+        let time = time.ok_or_else(|| malformed("missing 'time'"))?;
+        let message = message.ok_or_else(|| malformed("missing 'msg'"))?;
+
+        let mut log = Log::new(parse_status(&status)?, message, component, self.parse_time(&time)?);
+        log.thread = thread;
+        log.sequence = sequence;
+        log.repeated = repeated;
+        log.fields = fields;
+        Ok(log)
+    }
+
+    /// Время в любом из форматов `TimeFormat`, кроме произвольного strftime
+    pub fn parse_time(&self, text: &str) -> Result<LogTime, LogParseError> {
+        let invalid = || LogParseError::InvalidTime {
+            line: 1,
+            time: text.to_string(),
+        };
+        let base = &self.base_time.datetime;
+
+        if let Some(seconds) = text.strip_prefix(['+', '-']).and_then(|rest| rest.strip_suffix('s')) {
+            let seconds: f64 = seconds.parse().map_err(|_| invalid())?;
+            let millis = (seconds * 1000.0).round() as i64;
+            let offset = TimeDelta::milliseconds(if text.starts_with('-') { -millis } else { millis });
+            return Ok(LogTime::from_datetime(*base + offset));
+        }
+        if let Ok(time) = LogTime::parse_rfc3339(text) {
+            return Ok(time);
+        }
+
+        let naive = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f")
+            .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M"))
+            .or_else(|_| {
+                NaiveTime::parse_from_str(text, "%H:%M:%S%.f")
+                    .or_else(|_| NaiveTime::parse_from_str(text, "%H:%M"))
+                    .map(|time| base.date_naive().and_time(time))
+            })
+            .map_err(|_| invalid())?;
+        base.timezone()
+            .from_local_datetime(&naive)
+            .single()
+            .map(LogTime::from_datetime)
+            .ok_or_else(invalid)
+    }
+}
+
+/// Стиль, в котором записана строка лога
+///
+/// Многострочную запись нужно передавать целиком: в Tiny/Flat место и время
+/// стоят в конце последней строки.
+pub fn detect_style(line: &str) -> Option<LoggerPrintStyle> {
+    let line = line.trim_end();
+    if line.starts_with('{') {
+        Some(LoggerPrintStyle::Json)
+    } else if line.starts_with("time=") {
+        Some(LoggerPrintStyle::Logfmt)
+    } else if line.starts_with('[') && line.contains("]: ") {
+        Some(LoggerPrintStyle::Full)
+    } else if line.contains(" | from ") && line.contains(", time is ") {
+        Some(LoggerPrintStyle::Tiny)
+    } else if line.contains(" | file ") && line.contains(" | time ") {
+        Some(LoggerPrintStyle::Flat)
+    } else {
+        None
+    }
+}
+
+fn parse_status(text: &str) -> Result<Status, LogParseError> {
+    Status::from_name(text.trim()).map_err(|_| LogParseError::InvalidStatus {
+        line: 1,
+        status: text.to_string(),
+    })
+}
+
+/// Сообщение без пометки `(repeated N times)`, число повторов переносится в лог
+fn set_message(log: &mut Log, message: &str) {
+    let repeated = message.strip_suffix(" times)").or_else(|| message.strip_suffix(" time)"));
+    if let Some((text, count)) = repeated.and_then(|rest| rest.rsplit_once(" (repeated ")) {
+        if let Ok(count) = count.parse() {
+            log.message = text.to_string();
+            log.repeated = count;
+            return;
+        }
+    }
+    log.message = message.to_string();
+}
+
+/// Значение поля из текста: число, логическое значение или строка
+fn parse_field_value(text: &str) -> FieldValue {
+    if let Ok(value) = text.parse::<i64>() {
+        FieldValue::Int(value)
+    } else if let Ok(value) = text.parse::<u64>() {
+        FieldValue::UInt(value)
+    } else if let Ok(value) = text.parse::<bool>() {
+        FieldValue::Bool(value)
+    } else if text.contains(|ch: char| ch.is_ascii_digit()) && text.parse::<f64>().is_ok() {
+        FieldValue::Float(text.parse().unwrap_or_default())
+    } else {
+        FieldValue::Str(text.to_string())
+    }
+}
+
+/// Разбор строки из пар `key=value key2="value 2"`, `None` если есть что-то ещё
+fn parse_logfmt_pairs(text: &str) -> Option<Vec<(String, String)>> {
+    let mut pairs = Vec::new();
+    let mut rest = text.trim_start_matches(' ');
+
+    while !rest.is_empty() {
+        let (key, after) = rest.split_once('=')?;
+        let valid_key = !key.is_empty()
+            && key
+                .chars()
+                .all(|ch| ch.is_alphanumeric() || matches!(ch, '_' | '-' | '.' | ':'));
+        if !valid_key {
+            return None;
+        }
+
+        let (value, after) = if after.starts_with('"') {
+            let (value, consumed) = json::parse_string(after).ok()?;
+            (value, &after[consumed..])
+        } else {
+            let end = after.find(' ').unwrap_or(after.len());
+            let value = &after[..end];
+            if value.is_empty() || value.contains(['=', '"']) {
+                return None;
+            }
+            (value.to_string(), &after[end..])
+        };
+
+        if !after.is_empty() && !after.starts_with(' ') {
+            return None;
+        }
+        pairs.push((key.to_string(), value));
+        rest = after.trim_start_matches(' ');
+    }
+
+    if pairs.is_empty() {
+        None
+    } else {
+        Some(pairs)
+    }
+}
+
+/// Минимальный разборщик JSON для строк `log_to_json`
+mod json {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Value {
+        Null,
+        Bool(bool),
+        /// Число в исходной записи, чтобы не терять точность
+        Number(String),
+        Str(String),
+        Array(Vec<Value>),
+        Object(Vec<(String, Value)>),
+    }
+
+    impl Value {
+        pub fn as_str(&self) -> Option<&str> {
+            match self {
+                Value::Str(text) => Some(text),
+                _ => None,
+            }
+        }
+
+        pub fn as_number(&self) -> Option<&str> {
+            match self {
+                Value::Number(number) => Some(number),
+                _ => None,
+            }
+        }
+
+        pub fn as_object(&self) -> Option<&[(String, Value)]> {
+            match self {
+                Value::Object(entries) => Some(entries),
+                _ => None,
+            }
+        }
+    }
+
+    pub fn get<'a>(object: &'a [(String, Value)], key: &str) -> Option<&'a Value> {
+        object
+            .iter()
+            .find(|(entry_key, _)| entry_key == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Разбор документа целиком
+    pub fn parse(text: &str) -> Result<Value, String> {
+        let mut parser = Parser { text, pos: 0, depth: 0 };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos != text.len() {
+            return Err(format!("unexpected trailing data at byte {}", parser.pos));
+        }
+        Ok(value)
+    }
+
+    /// Разбор строки в кавычках в начале `text`, возвращает строку и длину в байтах
+    pub fn parse_string(text: &str) -> Result<(String, usize), String> {
+        let mut parser = Parser { text, pos: 0, depth: 0 };
+        let value = parser.string()?;
+        Ok((value, parser.pos))
+    }
+
+    /// Наибольшая вложенность массивов и объектов, глубже — ошибка, а не переполнение стека
+    const MAX_DEPTH: usize = 64;
+
+    struct Parser<'a> {
+        text: &'a str,
+        pos: usize,
+        depth: usize,
+    }
+
+    impl Parser<'_> {
+        fn peek(&self) -> Option<char> {
+            self.text[self.pos..].chars().next()
+        }
+
+        fn next(&mut self) -> Option<char> {
+            let ch = self.peek()?;
+            self.pos += ch.len_utf8();
+            Some(ch)
+        }
+
+        fn skip_whitespace(&mut self) {
+            while self.peek().is_some_and(|ch| ch.is_ascii_whitespace()) {
+                self.pos += 1;
+            }
+        }
+
+        fn expect(&mut self, expected: char) -> Result<(), String> {
+            match self.next() {
+                Some(ch) if ch == expected => Ok(()),
+                Some(ch) => Err(format!("expected '{}', found '{}' at byte {}", expected, ch, self.pos - ch.len_utf8())),
+                None => Err(format!("expected '{}', found end of line", expected)),
+            }
+        }
+
+        fn value(&mut self) -> Result<Value, String> {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(ch @ ('{' | '[')) => {
+                    if self.depth >= MAX_DEPTH {
+                        return Err(format!("nesting deeper than {} levels at byte {}", MAX_DEPTH, self.pos));
+                    }
+                    self.depth += 1;
+                    let value = if ch == '{' { self.object() } else { self.array() };
+                    self.depth -= 1;
+                    value
+                }
+                Some('"') => self.string().map(Value::Str),
+                Some('t') => self.literal("true", Value::Bool(true)),
+                Some('f') => self.literal("false", Value::Bool(false)),
+                Some('n') => self.literal("null", Value::Null),
+                Some(ch) if ch == '-' || ch.is_ascii_digit() => Ok(self.number()),
+                Some(ch) => Err(format!("unexpected '{}' at byte {}", ch, self.pos)),
+                None => Err("unexpected end of line".to_string()),
+            }
+        }
+
+        fn literal(&mut self, word: &str, value: Value) -> Result<Value, String> {
+            if self.text[self.pos..].starts_with(word) {
+                self.pos += word.len();
+                Ok(value)
+            } else {
+                Err(format!("invalid literal at byte {}", self.pos))
+            }
+        }
+
+        fn number(&mut self) -> Value {
+            let start = self.pos;
+            while self
+                .peek()
+                .is_some_and(|ch| ch.is_ascii_digit() || matches!(ch, '-' | '+' | '.' | 'e' | 'E'))
+            {
+                self.pos += 1;
+            }
+            Value::Number(self.text[start..self.pos].to_string())
+        }
+
+        fn string(&mut self) -> Result<String, String> {
+            self.expect('"')?;
+            let mut result = String::new();
+
+            loop {
+                match self.next() {
+                    Some('"') => return Ok(result),
+                    Some('\\') => match self.next() {
+                        Some('"') => result.push('"'),
+                        Some('\\') => result.push('\\'),
+                        Some('/') => result.push('/'),
+                        Some('n') => result.push('\n'),
+                        Some('r') => result.push('\r'),
+                        Some('t') => result.push('\t'),
+                        Some('b') => result.push('\u{08}'),
+                        Some('f') => result.push('\u{0C}'),
+                        Some('u') => {
+                            let mut code = self.hex_escape()?;
+                            // Символ вне BMP записывается суррогатной парой `\uD83D\uDE00`
+                            if (0xD800..0xDC00).contains(&code) && self.text[self.pos..].starts_with("\\u") {
+                                let start = self.pos;
+                                self.pos += 2;
+                                let low = self.hex_escape()?;
+                                if (0xDC00..0xE000).contains(&low) {
+                                    code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                                } else {
+                                    self.pos = start;
+                                }
+                            }
+                            result.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                        }
+                        Some(ch) => return Err(format!("invalid escape '\\{}'", ch)),
+                        None => return Err("unterminated string".to_string()),
+                    },
+                    Some(ch) => result.push(ch),
+                    None => return Err("unterminated string".to_string()),
+                }
+            }
+        }
+
+        /// Четыре шестнадцатеричные цифры после `\u`
+        fn hex_escape(&mut self) -> Result<u32, String> {
+            let hex = self.text.get(self.pos..self.pos + 4).ok_or("truncated \\u escape")?;
+            let code = u32::from_str_radix(hex, 16).map_err(|_| format!("invalid \\u escape '{}'", hex))?;
+            self.pos += 4;
+            Ok(code)
+        }
+
+        fn array(&mut self) -> Result<Value, String> {
+            self.expect('[')?;
+            let mut items = Vec::new();
+            self.skip_whitespace();
+            if self.peek() == Some(']') {
+                self.pos += 1;
+                return Ok(Value::Array(items));
+            }
+
+            loop {
+                items.push(self.value()?);
+                self.skip_whitespace();
+                match self.next() {
+                    Some(',') => continue,
+                    Some(']') => return Ok(Value::Array(items)),
+                    _ => return Err(format!("expected ',' or ']' at byte {}", self.pos)),
+                }
+            }
+        }
+
+        fn object(&mut self) -> Result<Value, String> {
+            self.expect('{')?;
+            let mut entries = Vec::new();
+            self.skip_whitespace();
+            if self.peek() == Some('}') {
+                self.pos += 1;
+                return Ok(Value::Object(entries));
+            }
+
+            loop {
+                self.skip_whitespace();
+                let key = self.string()?;
+                self.skip_whitespace();
+                self.expect(':')?;
+                entries.push((key, self.value()?));
+                self.skip_whitespace();
+                match self.next() {
+                    Some(',') => continue,
+                    Some('}') => return Ok(Value::Object(entries)),
+                    _ => return Err(format!("expected ',' or '}}' at byte {}", self.pos)),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,150 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/log_query.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+use crate::common_types::Status;
+use crate::logger::{Log, LogTime, Logger};
+
+/// Условия выборки логов
+///
+/// Все заданные условия должны выполняться одновременно.
+#[derive(Debug, Clone, Default)]
+pub struct LogQuery {
+    pub statuses: Vec<Status>,
+    pub min_status: Option<Status>,
+    pub file_name: Option<String>,
+    pub func_name: Option<String>,
+    pub dir_path: Option<String>,
+    /// Начало диапазона времени, включительно
+    pub since: Option<LogTime>,
+    /// Конец диапазона времени, не включительно
+    pub until: Option<LogTime>,
+    pub contains: Option<String>,
+    #[cfg(feature = "regex")]
+    pub pattern: Option<regex::Regex>,
+}
+
+impl LogQuery {
+    /// Пустой запрос, подходит любой лог
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Лог с одним из указанных статусов (можно вызывать несколько раз)
+    pub fn status(mut self, status: Status) -> Self {
+        self.statuses.push(status);
+        self
+    }
+
+    /// Лог не ниже указанного статуса
+    pub fn min_status(mut self, status: Status) -> Self {
+        self.min_status = Some(status);
+        self
+    }
+
+    /// Лог из файла с этим именем
+    pub fn file(mut self, file_name: &str) -> Self {
+        self.file_name = Some(file_name.to_string());
+        self
+    }
+
+    /// Лог из функции с этим именем
+    pub fn func(mut self, func_name: &str) -> Self {
+        self.func_name = Some(func_name.to_string());
+        self
+    }
+
+    /// Лог из каталога или любого его подкаталога
+    pub fn dir(mut self, dir_path: &str) -> Self {
+        self.dir_path = Some(dir_path.trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Лог не раньше указанного времени
+    pub fn since(mut self, time: LogTime) -> Self {
+        self.since = Some(time);
+        self
+    }
+
+    /// Лог раньше указанного времени
+    pub fn until(mut self, time: LogTime) -> Self {
+        self.until = Some(time);
+        self
+    }
+
+    /// Сообщение содержит подстроку
+    pub fn contains(mut self, text: &str) -> Self {
+        self.contains = Some(text.to_string());
+        self
+    }
+
+    /// Сообщение подходит под регулярное выражение
+    #[cfg(feature = "regex")]
+    pub fn matches_regex(mut self, pattern: &str) -> Result<Self, regex::Error> {
+        self.pattern = Some(regex::Regex::new(pattern)?);
+        Ok(self)
+    }
+
+    /// Подходит ли лог под все условия
+    pub fn matches(&self, log: &Log) -> bool {
+        if !self.statuses.is_empty() && !self.statuses.contains(&log.status) {
+            return false;
+        }
+        if self.min_status.is_some_and(|min| log.status.severity() < min.severity()) {
+            return false;
+        }
+        if self.file_name.as_ref().is_some_and(|file| *file != log.component.file_name) {
+            return false;
+        }
+        if self.func_name.as_ref().is_some_and(|func| *func != log.component.func_name) {
+            return false;
+        }
+        if let Some(dir) = &self.dir_path {
+            let log_dir = log.component.dir_path.trim_end_matches('/');
+            let nested = log_dir
+                .strip_prefix(dir.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'));
+            if !nested {
+                return false;
+            }
+        }
+        if self.since.is_some_and(|since| log.time < since) {
+            return false;
+        }
+        if self.until.is_some_and(|until| log.time >= until) {
+            return false;
+        }
+        if self.contains.as_ref().is_some_and(|text| !log.message.contains(text.as_str())) {
+            return false;
+        }
+        #[cfg(feature = "regex")]
+        if self.pattern.as_ref().is_some_and(|pattern| !pattern.is_match(&log.message)) {
+            return false;
+        }
+
+        true
+    }
+
+    /// Отбор подходящих логов из любого набора
+    pub fn filter<'a, I>(&'a self, logs: I) -> impl Iterator<Item = &'a Log> + 'a
+    where
+        I: IntoIterator<Item = &'a Log>,
+        I::IntoIter: 'a,
+    {
+        logs.into_iter().filter(move |log| self.matches(log))
+    }
+}
+
+impl Logger {
+    /// Логи из памяти, подходящие под запрос
+    pub fn query<'a>(&'a self, query: &'a LogQuery) -> impl Iterator<Item = &'a Log> + 'a {
+        query.filter(&self.logs)
+    }
+}
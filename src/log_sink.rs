@@ -0,0 +1,25 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/log_sink.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+use std::io;
+
+use crate::logger::Log;
+
+/// Приёмник логов, в который логгер пишет каждый лог сразу после добавления
+pub trait LogSink: Send {
+    /// Запись лога, `line` — лог в стиле логгера без цветов
+    fn write_log(&mut self, log: &Log, line: &str) -> io::Result<()>;
+
+    /// Сброс буферов приёмника
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,209 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/log_span.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+use chrono::TimeDelta;
+use std::borrow::Cow;
+use std::ops::{Deref, DerefMut};
+use std::panic::Location;
+use std::path::Path;
+
+use crate::common_types::Status;
+use crate::log_summary::format_duration;
+use crate::logger::{Component, Log, LogTime, Logger};
+
+/// Место лога в этапе, записанное самим этапом
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpanEdge {
+    Start,
+    End,
+}
+
+/// Замер времени этапа, записывает начало и конец этапа в логгер
+///
+/// Пока этап открыт, логгер доступен через него самого, поэтому вложенные
+/// этапы создаются из внешнего. Глубина хранится в `Log::span_depth`,
+/// и при выводе в терминал все логи этапа получают отступ:
+///
+/// ```text
+/// ╭─ build
+/// │  ╭─ parse
+/// │  │  3 files
+/// │  ╰─ parse finished in 0.412s
+/// ╰─ build finished in 1.305s
+/// ```
+///
+/// Как и `add_log`, этап не печатает сам: строки для терминала возвращают
+/// `take_output`, `end` и `finish`. Если guard просто уничтожен, конец этапа
+/// записывается со статусом `Ok`, если не задан другой, или `Error`, если
+/// поток паникует, а его строка не выводится.
+pub struct Span<'a> {
+    logger: &'a mut Logger,
+    name: String,
+    component: Component,
+    start: LogTime,
+    depth: usize,
+    status: Option<Status>,
+    output: Option<String>,
+    ended: bool,
+}
+
+impl Logger {
+    /// Начало этапа, компонент — место вызова, функция — имя этапа
+    #[track_caller]
+    pub fn span(&mut self, name: &str) -> Span<'_> {
+        let location = Location::caller();
+        let path = Path::new(location.file());
+        let component = Component::new(
+            path.file_name()
+                .map(|file| file.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            name.to_string(),
+            path.parent()
+                .map(|dir| dir.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        )
+        .with_line(location.line());
+
+        self.span_with_component(name, component)
+    }
+
+    /// Начало этапа с явным компонентом
+    pub fn span_with_component(&mut self, name: &str, component: Component) -> Span<'_> {
+        let depth = self.span_depth;
+        let start = self.now();
+
+        let mut log = Log::new(Status::Info, name.to_string(), component.clone(), start)
+            .with_field("span", name);
+        log.span_edge = Some(SpanEdge::Start);
+        let output = self.add_entry(log);
+        self.span_depth += 1;
+
+        Span {
+            logger: self,
+            name: name.to_string(),
+            component,
+            start,
+            depth,
+            status: None,
+            output,
+            ended: false,
+        }
+    }
+}
+
+impl Span<'_> {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Время с начала этапа
+    pub fn elapsed(&self) -> TimeDelta {
+        self.logger.now().elapsed_since(&self.start)
+    }
+
+    /// Статус, с которым этап будет завершён
+    pub fn set_status(&mut self, status: Status) {
+        self.status = Some(status);
+    }
+
+    /// Строки для терминала о начале этапа, отдаются один раз
+    pub fn take_output(&mut self) -> Option<String> {
+        self.output.take()
+    }
+
+    /// Завершение этапа, возвращает строки для терминала
+    pub fn end(mut self) -> Option<String> {
+        self.close()
+    }
+
+    /// Завершение этапа с указанным статусом, возвращает строки для терминала
+    pub fn finish(mut self, status: Status) -> Option<String> {
+        self.status = Some(status);
+        self.close()
+    }
+
+    /// Запись конца этапа
+    fn close(&mut self) -> Option<String> {
+        self.ended = true;
+        let status = match self.status {
+            Some(status) => status,
+            None if std::thread::panicking() => Status::Error,
+            None => Status::Ok,
+        };
+
+        let end = self.logger.now();
+        let elapsed = end.elapsed_since(&self.start);
+        let message = format!("{} finished in {}", self.name, format_duration(elapsed));
+
+        let mut log = Log::new(status, message, self.component.clone(), end)
+            .with_field("span", self.name.as_str())
+            .with_field("elapsed_ms", elapsed.num_milliseconds());
+        log.span_edge = Some(SpanEdge::End);
+
+        self.logger.span_depth = self.depth;
+        self.logger.add_entry(log)
+    }
+}
+
+impl Log {
+    /// Сообщение с отступом этапов и ветками `╭─`/`╰─` для вывода в терминал
+    ///
+    /// Строки продолжения многострочного сообщения получают тот же отступ.
+    pub fn span_message(&self) -> Cow<'_, str> {
+        if self.span_depth == 0 && self.span_edge.is_none() {
+            return Cow::Borrowed(&self.message);
+        }
+
+        let indent = span_indent(self.span_depth);
+        let (first, rest) = match self.span_edge {
+            Some(SpanEdge::Start) => (format!("{}╭─ ", indent), format!("{}│  ", indent)),
+            Some(SpanEdge::End) => (format!("{}╰─ ", indent), format!("{}   ", indent)),
+            None => (indent.clone(), indent),
+        };
+
+        let mut result = String::new();
+        for (index, line) in self.message.split('\n').enumerate() {
+            if index > 0 {
+                result.push('\n');
+            }
+            result.push_str(if index == 0 { &first } else { &rest });
+            result.push_str(line);
+        }
+        Cow::Owned(result)
+    }
+}
+
+impl Deref for Span<'_> {
+    type Target = Logger;
+
+    fn deref(&self) -> &Logger {
+        self.logger
+    }
+}
+
+impl DerefMut for Span<'_> {
+    fn deref_mut(&mut self) -> &mut Logger {
+        self.logger
+    }
+}
+
+impl Drop for Span<'_> {
+    fn drop(&mut self) {
+        if !self.ended {
+            self.close();
+        }
+    }
+}
+
+/// Отступ этапа с глубиной вложенности `depth`
+fn span_indent(depth: usize) -> String {
+    "│  ".repeat(depth)
+}
@@ -0,0 +1,243 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/log_summary.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+use chrono::TimeDelta;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::colors::{RESET_COLOR, STYLE_BOLD, STYLE_FADED};
+use crate::common_types::Status;
+use crate::log_formats::escape_json;
+use crate::logger::{Log, LogTime};
+use crate::theme_config::DisplayConfig;
+
+/// Итоги работы логгера
+#[derive(Debug, Clone)]
+pub struct LogSummary {
+    /// Число логов по статусам
+    pub status_counts: BTreeMap<Status, usize>,
+    /// Число логов по компонентам (`dir/file`)
+    pub component_counts: BTreeMap<String, usize>,
+    pub first_error: Option<Log>,
+    pub last_error: Option<Log>,
+    pub total: usize,
+    pub start: LogTime,
+    pub end: LogTime,
+}
+
+impl LogSummary {
+    /// Пустые итоги, начиная с `start`
+    pub fn new(start: LogTime) -> Self {
+        Self {
+            status_counts: BTreeMap::new(),
+            component_counts: BTreeMap::new(),
+            first_error: None,
+            last_error: None,
+            total: 0,
+            start,
+            end: start,
+        }
+    }
+
+    /// Итоги по готовому набору логов
+    pub fn from_logs<'a>(logs: impl IntoIterator<Item = &'a Log>, start: LogTime, end: LogTime) -> Self {
+        let mut summary = Self::new(start);
+        for log in logs {
+            summary.record(log);
+        }
+        summary.end = end;
+        summary
+    }
+
+    /// Учёт одного лога
+    pub fn record(&mut self, log: &Log) {
+        self.total += 1;
+        *self.status_counts.entry(log.status).or_default() += 1;
+        *self.component_counts.entry(component_key(log)).or_default() += 1;
+
+        if matches!(log.status, Status::Error | Status::Fatal) {
+            if self.first_error.is_none() {
+                self.first_error = Some(log.clone());
+            }
+            self.last_error = Some(log.clone());
+        }
+    }
+
+    /// Число логов с этим статусом
+    pub fn count(&self, status: Status) -> usize {
+        self.status_counts.get(&status).copied().unwrap_or_default()
+    }
+
+    /// Число ошибок, включая фатальные
+    pub fn errors(&self) -> usize {
+        self.count(Status::Error) + self.count(Status::Fatal)
+    }
+
+    pub fn warnings(&self) -> usize {
+        self.count(Status::Warn)
+    }
+
+    /// Время работы логгера
+    pub fn runtime(&self) -> TimeDelta {
+        self.end.elapsed_since(&self.start)
+    }
+
+    /// Итоговая строка: `3 errors, 5 warnings emitted`
+    pub fn headline(&self) -> String {
+        format!(
+            "{}, {} emitted",
+            plural(self.errors(), "error"),
+            plural(self.warnings(), "warning")
+        )
+    }
+
+    /// Итоги в виде блока, цвета применяются только при наличии конфигурации
+    pub fn render(&self, config: Option<&DisplayConfig>) -> String {
+        let paint = |text: String, code: &str| match config {
+            Some(_) => format!("{}{}{}", code, text, RESET_COLOR),
+            None => text,
+        };
+
+        let headline = match config {
+            Some(config) => {
+                let status = if self.errors() > 0 {
+                    Status::Error
+                } else if self.warnings() > 0 {
+                    Status::Warn
+                } else {
+                    Status::Ok
+                };
+                format!(
+                    "{}{}{} {}{}",
+                    STYLE_BOLD,
+                    config.color_theme.for_status(status).as_str(),
+                    config.icons_theme.for_status(status),
+                    self.headline(),
+                    RESET_COLOR
+                )
+            }
+            None => self.headline(),
+        };
+
+        let statuses = self
+            .status_counts
+            .iter()
+            .rev()
+            .map(|(status, count)| format!("{} {}", status, count))
+            .collect::<Vec<_>>()
+            .join(" · ");
+        let components = self
+            .component_counts
+            .iter()
+            .map(|(component, count)| format!("{} {}", component, count))
+            .collect::<Vec<_>>()
+            .join(" · ");
+
+        let mut lines = vec![
+            format!("╭─ {}", paint("Summary".to_string(), STYLE_BOLD)),
+            format!("│ {}", headline),
+            format!("│ {} {}", paint("Statuses:   ".to_string(), STYLE_FADED), statuses),
+            format!("│ {} {}", paint("Components: ".to_string(), STYLE_FADED), components),
+        ];
+        for (label, log) in [("First error:", &self.first_error), ("Last error: ", &self.last_error)] {
+            if let Some(log) = log {
+                lines.push(format!(
+                    "│ {} [{}] {}: {}",
+                    paint(format!("{:<11}", label), STYLE_FADED),
+                    log.time.format(),
+                    log.component.file_name,
+                    log.message
+                ));
+            }
+        }
+        lines.push(format!(
+            "│ {} {}",
+            paint("Runtime:    ".to_string(), STYLE_FADED),
+            format_duration(self.runtime())
+        ));
+        lines.push("╰─".to_string());
+
+        lines.join("\n")
+    }
+
+    /// Итоги одной строкой JSON
+    pub fn to_json(&self) -> String {
+        let statuses = self
+            .status_counts
+            .iter()
+            .map(|(status, count)| format!("\"{}\":{}", escape_json(&status.to_string()), count))
+            .collect::<Vec<_>>()
+            .join(",");
+        let components = self
+            .component_counts
+            .iter()
+            .map(|(component, count)| format!("\"{}\":{}", escape_json(component), count))
+            .collect::<Vec<_>>()
+            .join(",");
+        let error = |log: &Option<Log>| match log {
+            Some(log) => format!("\"{}\"", escape_json(&log.message)),
+            None => "null".to_string(),
+        };
+
+        format!(
+            "{{\"total\":{},\"errors\":{},\"warnings\":{},\"statuses\":{{{}}},\"components\":{{{}}},\"first_error\":{},\"last_error\":{},\"start\":\"{}\",\"end\":\"{}\",\"runtime_ms\":{}}}",
+            self.total,
+            self.errors(),
+            self.warnings(),
+            statuses,
+            components,
+            error(&self.first_error),
+            error(&self.last_error),
+            self.start.to_rfc3339(),
+            self.end.to_rfc3339(),
+            self.runtime().num_milliseconds()
+        )
+    }
+}
+
+impl fmt::Display for LogSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(None))
+    }
+}
+
+/// Ключ компонента в итогах
+fn component_key(log: &Log) -> String {
+    let dir = log.component.dir_path.trim_end_matches('/');
+    if dir.is_empty() {
+        log.component.file_name.clone()
+    } else {
+        format!("{}/{}", dir, log.component.file_name)
+    }
+}
+
+fn plural(count: usize, word: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, word)
+    } else {
+        format!("{} {}s", count, word)
+    }
+}
+
+/// Длительность в виде `1h 02m 03.456s`, `2m 03.456s` или `3.456s`
+pub(crate) fn format_duration(duration: TimeDelta) -> String {
+    let millis = duration.num_milliseconds().max(0);
+    let (hours, minutes) = (millis / 3_600_000, millis / 60_000 % 60);
+    let seconds = format!("{}.{:03}s", millis / 1000 % 60, millis % 1000);
+
+    if hours > 0 {
+        format!("{}h {:02}m {:0>7}", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {:0>7}", minutes, seconds)
+    } else {
+        seconds
+    }
+}
@@ -0,0 +1,339 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/log_syslog.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+use std::io;
+use std::os::unix::net::UnixDatagram;
+use std::path::{Path, PathBuf};
+
+use crate::common_types::Status;
+use crate::log_formats::{logfmt_field_key, quote_logfmt};
+use crate::log_sink::LogSink;
+use crate::logger::Log;
+
+/// Сокет системного журнала по умолчанию
+pub const SYSLOG_SOCKET: &str = "/dev/log";
+
+/// Наибольший размер сообщения по умолчанию, длинные сообщения обрезаются
+pub const SYSLOG_MAX_MESSAGE_LEN: usize = 8192;
+
+/// Размер, до которого сообщение обрезается, если сокет отверг его как слишком длинное
+///
+/// RFC 5424 требует, чтобы получатели принимали сообщения хотя бы такой длины.
+const SYSLOG_FALLBACK_MESSAGE_LEN: usize = 2048;
+
+/// Источник сообщений syslog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyslogFacility {
+    User,
+    Daemon,
+    Local0,
+    Local1,
+    Local2,
+    Local3,
+    Local4,
+    Local5,
+    Local6,
+    Local7,
+}
+
+impl SyslogFacility {
+    /// Код источника по RFC 5424
+    pub fn code(&self) -> u8 {
+        match self {
+            SyslogFacility::User => 1,
+            SyslogFacility::Daemon => 3,
+            SyslogFacility::Local0 => 16,
+            SyslogFacility::Local1 => 17,
+            SyslogFacility::Local2 => 18,
+            SyslogFacility::Local3 => 19,
+            SyslogFacility::Local4 => 20,
+            SyslogFacility::Local5 => 21,
+            SyslogFacility::Local6 => 22,
+            SyslogFacility::Local7 => 23,
+        }
+    }
+}
+
+/// Важность syslog для статуса лога
+///
+/// `Fatal` — critical, `Ok` — notice, пользовательские статусы — informational.
+pub fn syslog_severity(status: Status) -> u8 {
+    match status {
+        Status::Fatal => 2,
+        Status::Error => 3,
+        Status::Warn => 4,
+        Status::Ok => 5,
+        Status::Info | Status::Custom(_) => 6,
+        Status::Debug | Status::Trace => 7,
+    }
+}
+
+/// Приёмник, отправляющий логи в системный журнал сообщениями RFC 5424
+/// через локальный датаграммный сокет
+///
+/// ```text
+/// <11>1 2025-03-07T14:30:45.250+03:00 buildhost builder 4242 parser.rs [component@32473 file="parser.rs" func="parse" dir="src"] unexpected token
+/// ```
+///
+/// Имя файла компонента становится полем MSGID. Компонент целиком и поля лога
+/// попадают в structured data, только если задан номер предприятия
+/// (`enterprise_id`): без своего номера собственные элементы писать нельзя,
+/// поэтому они дописываются к тексту сообщения парами logfmt.
+pub struct SyslogSink {
+    socket: UnixDatagram,
+    path: PathBuf,
+    facility: SyslogFacility,
+    hostname: String,
+    app_name: String,
+    proc_id: String,
+    enterprise_id: Option<u32>,
+    max_message_len: usize,
+}
+
+impl SyslogSink {
+    /// Подключение к `/dev/log`
+    pub fn connect() -> io::Result<Self> {
+        Self::connect_to(SYSLOG_SOCKET)
+    }
+
+    /// Подключение к указанному сокету
+    pub fn connect_to(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let socket = UnixDatagram::unbound()?;
+        socket.connect(&path)?;
+
+        let hostname = nix::unistd::gethostname()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let app_name = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.file_stem().map(|name| name.to_string_lossy().into_owned()))
+            .unwrap_or_default();
+
+        Ok(Self {
+            socket,
+            path,
+            facility: SyslogFacility::User,
+            hostname,
+            app_name,
+            proc_id: std::process::id().to_string(),
+            enterprise_id: None,
+            max_message_len: SYSLOG_MAX_MESSAGE_LEN,
+        })
+    }
+
+    pub fn facility(mut self, facility: SyslogFacility) -> Self {
+        self.facility = facility;
+        self
+    }
+
+    /// Имя приложения, по умолчанию — имя исполняемого файла
+    pub fn app_name(mut self, name: &str) -> Self {
+        self.app_name = name.to_string();
+        self
+    }
+
+    /// Имя хоста, по умолчанию — системное
+    pub fn hostname(mut self, name: &str) -> Self {
+        self.hostname = name.to_string();
+        self
+    }
+
+    /// Номер предприятия (IANA Private Enterprise Number) для элементов
+    /// structured data `component@<номер>` и `fields@<номер>`
+    pub fn enterprise_id(mut self, number: u32) -> Self {
+        self.enterprise_id = Some(number);
+        self
+    }
+
+    /// Наибольший размер сообщения в байтах, по умолчанию `SYSLOG_MAX_MESSAGE_LEN`
+    pub fn max_message_len(mut self, bytes: usize) -> Self {
+        self.max_message_len = bytes;
+        self
+    }
+
+    /// Сообщение RFC 5424 для лога, не длиннее `max_message_len`
+    pub fn format(&self, log: &Log) -> String {
+        self.format_within(log, self.max_message_len)
+    }
+
+    /// Сообщение не длиннее `max_len` байт: обрезается текст лога,
+    /// а если не помещается даже заголовок — structured data
+    fn format_within(&self, log: &Log, max_len: usize) -> String {
+        let priority = self.facility.code() as u32 * 8 + syslog_severity(log.status) as u32;
+        let header = format!(
+            "<{}>1 {} {} {} {} {}",
+            priority,
+            log.time.to_rfc3339(),
+            header_field(&self.hostname, 255),
+            header_field(&self.app_name, 48),
+            header_field(&self.proc_id, 128),
+            header_field(&log.component.file_name, 32),
+        );
+
+        let data = match self.enterprise_id {
+            Some(number) => structured_data(log, number),
+            None => "-".to_string(),
+        };
+        let mut message = format!("{} {} ", header, data);
+        if message.len() > max_len {
+            message = format!("{} - ", header);
+        }
+
+        let mut text = log.display_message().into_owned();
+        if self.enterprise_id.is_none() {
+            text.push_str(&logfmt_pairs(log));
+        }
+        let budget = max_len.saturating_sub(message.len());
+        message.push_str(truncate(&text, budget));
+        truncate(&message, max_len).to_string()
+    }
+
+    /// Отправка с одним переподключением, если журнал был перезапущен
+    fn send(&mut self, message: &str) -> io::Result<()> {
+        match self.socket.send(message.as_bytes()) {
+            Ok(_) => Ok(()),
+            Err(error)
+                if matches!(
+                    error.kind(),
+                    io::ErrorKind::ConnectionRefused | io::ErrorKind::NotConnected | io::ErrorKind::NotFound
+                ) =>
+            {
+                self.reconnect()?;
+                self.socket.send(message.as_bytes()).map(|_| ())
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Повторное подключение, если журнал был перезапущен
+    fn reconnect(&mut self) -> io::Result<()> {
+        let socket = UnixDatagram::unbound()?;
+        socket.connect(&self.path)?;
+        self.socket = socket;
+        Ok(())
+    }
+}
+
+impl LogSink for SyslogSink {
+    fn write_log(&mut self, log: &Log, _line: &str) -> io::Result<()> {
+        let message = self.format(log);
+        match self.send(&message) {
+            // Сокет принимает датаграммы меньшего размера, чем `max_message_len`
+            Err(error)
+                if error.raw_os_error() == Some(nix::libc::EMSGSIZE)
+                    && message.len() > SYSLOG_FALLBACK_MESSAGE_LEN =>
+            {
+                let message = self.format_within(log, SYSLOG_FALLBACK_MESSAGE_LEN);
+                self.send(&message)
+            }
+            result => result,
+        }
+    }
+}
+
+/// Поле заголовка: только печатные символы ASCII без пробелов, `-` если пусто
+fn header_field(value: &str, max_len: usize) -> String {
+    let field: String = value
+        .chars()
+        .map(|c| if c.is_ascii_graphic() { c } else { '_' })
+        .take(max_len)
+        .collect();
+
+    if field.is_empty() {
+        "-".to_string()
+    } else {
+        field
+    }
+}
+
+/// Наибольшая длина строки в байтах, не разрывающая символ
+fn truncate(text: &str, max_len: usize) -> &str {
+    if text.len() <= max_len {
+        return text;
+    }
+    let mut end = max_len;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Компонент и поля лога в виде
+/// `[component@N file="..." func="..." dir="..." line="..."][fields@N key="value" ...]`
+fn structured_data(log: &Log, enterprise_id: u32) -> String {
+    let component = &log.component;
+    let mut result = format!(
+        "[component@{} file=\"{}\" func=\"{}\" dir=\"{}\"",
+        enterprise_id,
+        escape_param_value(&component.file_name),
+        escape_param_value(&component.func_name),
+        escape_param_value(&component.dir_path)
+    );
+    if let Some(line) = component.line {
+        result.push_str(&format!(" line=\"{}\"", line));
+    }
+    result.push(']');
+
+    if log.fields.is_empty() {
+        return result;
+    }
+
+    result.push_str(&format!("[fields@{}", enterprise_id));
+    for (key, value) in &log.fields {
+        let name: String = key
+            .chars()
+            .filter(|c| c.is_ascii_graphic() && !matches!(c, '=' | ']' | '"'))
+            .take(32)
+            .collect();
+        if name.is_empty() {
+            continue;
+        }
+        result.push_str(&format!(" {}=\"{}\"", name, escape_param_value(&value.to_string())));
+    }
+    result.push(']');
+    result
+}
+
+/// Компонент (кроме имени файла из MSGID) и поля в виде ` func=parse dir=src key=value`
+fn logfmt_pairs(log: &Log) -> String {
+    let component = &log.component;
+    let mut pairs = vec![
+        ("func".to_string(), component.func_name.clone()),
+        ("dir".to_string(), component.dir_path.clone()),
+    ];
+    pairs.retain(|(_, value)| !value.is_empty());
+    if let Some(line) = component.line {
+        pairs.push(("line".to_string(), line.to_string()));
+    }
+    pairs.extend(
+        log.fields
+            .iter()
+            .map(|(key, value)| (logfmt_field_key(key), value.to_string())),
+    );
+
+    pairs
+        .iter()
+        .map(|(key, value)| format!(" {}={}", key, quote_logfmt(value)))
+        .collect()
+}
+
+/// Экранирование `"`, `\` и `]` в значении параметра
+fn escape_param_value(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
+}
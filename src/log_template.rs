@@ -0,0 +1,417 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/log_template.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+use chrono::format::{Item, StrftimeItems};
+use std::fmt;
+
+use crate::colors::{RESET_COLOR, STYLE_FADED};
+use crate::log_formats::fields_to_logfmt;
+use crate::logger::{Log, TimeFormat};
+use crate::stylish::{process_style_tags, strip_style_tags};
+use crate::theme_config::DisplayConfig;
+
+/// Маркер места подстановки в скомпилированном шаблоне
+const PLACEHOLDER_MARKER: char = '\u{E000}';
+
+/// Формат времени по умолчанию для `{time}`
+const DEFAULT_TIME_FORMAT: &str = "%H:%M:%S";
+
+/// Поле лога, доступное в шаблоне
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateField {
+    /// `{time}` или `{time:<strftime>}`
+    Time(String),
+    /// `{status}`
+    Status,
+    /// `{message}`
+    Message,
+    /// `{component.file}`
+    File,
+    /// `{component.func}`
+    Func,
+    /// `{component.dir}`
+    Dir,
+    /// `{component.line}`
+    Line,
+    /// `{fields}` — поля в виде `key=value`
+    Fields,
+    /// `{thread}`
+    Thread,
+}
+
+/// Выравнивание значения в поле
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateAlign {
+    Left,
+    Right,
+    Center,
+}
+
+/// Спецификация ширины и выравнивания: `[[fill]align][width][.precision]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateSpec {
+    pub fill: char,
+    pub align: TemplateAlign,
+    pub width: Option<usize>,
+    pub precision: Option<usize>,
+}
+
+impl Default for TemplateSpec {
+    fn default() -> Self {
+        Self {
+            fill: ' ',
+            align: TemplateAlign::Left,
+            width: None,
+            precision: None,
+        }
+    }
+}
+
+impl TemplateSpec {
+    /// Разбор спецификации из строки после `:`
+    fn parse(spec: &str) -> Result<Self, TemplateError> {
+        let mut result = Self::default();
+        let chars: Vec<char> = spec.chars().collect();
+        let mut pos = 0;
+
+        let align_of = |ch: char| match ch {
+            '<' => Some(TemplateAlign::Left),
+            '>' => Some(TemplateAlign::Right),
+            '^' => Some(TemplateAlign::Center),
+            _ => None,
+        };
+
+        // Заполнитель указывается только вместе с выравниванием
+        if chars.len() >= 2 {
+            if let Some(align) = align_of(chars[1]) {
+                result.fill = chars[0];
+                result.align = align;
+                pos = 2;
+            }
+        }
+        if pos == 0 {
+            if let Some(align) = chars.first().copied().and_then(align_of) {
+                result.align = align;
+                pos = 1;
+            }
+        }
+
+        let rest: String = chars[pos..].iter().collect();
+        let (width, precision) = match rest.split_once('.') {
+            Some((width, precision)) => (width, Some(precision)),
+            None => (rest.as_str(), None),
+        };
+
+        if !width.is_empty() {
+            result.width = Some(
+                width
+                    .parse()
+                    .map_err(|_| TemplateError::InvalidSpec(spec.to_string()))?,
+            );
+        }
+        if let Some(precision) = precision {
+            result.precision = Some(
+                precision
+                    .parse()
+                    .map_err(|_| TemplateError::InvalidSpec(spec.to_string()))?,
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// Применение спецификации к значению
+    fn apply(&self, value: &str) -> String {
+        let value: String = match self.precision {
+            Some(precision) => value.chars().take(precision).collect(),
+            None => value.to_string(),
+        };
+
+        let len = value.chars().count();
+        let padding = match self.width {
+            Some(width) if width > len => width - len,
+            _ => return value,
+        };
+
+        let (left, right) = match self.align {
+            TemplateAlign::Left => (0, padding),
+            TemplateAlign::Right => (padding, 0),
+            TemplateAlign::Center => (padding / 2, padding - padding / 2),
+        };
+
+        let fill = self.fill.to_string();
+        format!("{}{}{}", fill.repeat(left), value, fill.repeat(right))
+    }
+}
+
+/// Место подстановки в шаблоне
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplatePlaceholder {
+    pub field: TemplateField,
+    pub spec: TemplateSpec,
+}
+
+/// Ошибка разбора шаблона
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// Незакрытая `{`
+    UnclosedPlaceholder(usize),
+    /// Одиночная `}` без пары
+    UnmatchedBrace(usize),
+    /// Неизвестное имя поля
+    UnknownField(String),
+    /// Некорректная спецификация ширины/выравнивания
+    InvalidSpec(String),
+    /// Некорректный формат времени
+    InvalidTimeFormat(String),
+    /// Подстановка оказалась внутри стилевого тега
+    PlaceholderInsideTag,
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::UnclosedPlaceholder(pos) => {
+                write!(f, "unclosed placeholder starting at {}", pos)
+            }
+            TemplateError::UnmatchedBrace(pos) => write!(f, "unmatched '}}' at {}", pos),
+            TemplateError::UnknownField(name) => write!(f, "unknown template field '{}'", name),
+            TemplateError::InvalidSpec(spec) => write!(f, "invalid format spec '{}'", spec),
+            TemplateError::InvalidTimeFormat(spec) => {
+                write!(f, "invalid time format '{}'", spec)
+            }
+            TemplateError::PlaceholderInsideTag => {
+                write!(f, "placeholder cannot be placed inside a style tag")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// Скомпилированный шаблон строки лога
+///
+/// Шаблон разбирается один раз, после чего используется для каждого лога.
+/// Текст между подстановками может содержать разметку `sty!`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogTemplate {
+    source: String,
+    placeholders: Vec<TemplatePlaceholder>,
+    styled_literals: Vec<String>,
+    plain_literals: Vec<String>,
+    active_styles: Vec<String>,
+}
+
+impl LogTemplate {
+    /// Разбор шаблона
+    pub fn parse(template: &str) -> Result<Self, TemplateError> {
+        let mut skeleton = String::new();
+        let mut placeholders = Vec::new();
+        let mut chars = template.char_indices().peekable();
+
+        while let Some((pos, ch)) = chars.next() {
+            match ch {
+                '{' if matches!(chars.peek(), Some((_, '{'))) => {
+                    chars.next();
+                    skeleton.push('{');
+                }
+                '}' if matches!(chars.peek(), Some((_, '}'))) => {
+                    chars.next();
+                    skeleton.push('}');
+                }
+                '{' => {
+                    let mut body = String::new();
+                    let mut closed = false;
+                    for (_, next_ch) in chars.by_ref() {
+                        if next_ch == '}' {
+                            closed = true;
+                            break;
+                        }
+                        body.push(next_ch);
+                    }
+                    if !closed {
+                        return Err(TemplateError::UnclosedPlaceholder(pos));
+                    }
+
+                    placeholders.push(parse_placeholder(&body)?);
+                    skeleton.push(PLACEHOLDER_MARKER);
+                }
+                '}' => return Err(TemplateError::UnmatchedBrace(pos)),
+                _ => skeleton.push(ch),
+            }
+        }
+
+        let styled = process_style_tags(&skeleton);
+        let plain = strip_style_tags(&skeleton);
+
+        let styled_literals: Vec<String> =
+            styled.split(PLACEHOLDER_MARKER).map(String::from).collect();
+        let plain_literals: Vec<String> =
+            plain.split(PLACEHOLDER_MARKER).map(String::from).collect();
+
+        if styled_literals.len() != placeholders.len() + 1
+            || plain_literals.len() != placeholders.len() + 1
+        {
+            return Err(TemplateError::PlaceholderInsideTag);
+        }
+
+        let active_styles = (0..placeholders.len())
+            .map(|index| active_style(&styled_literals[..=index].concat()))
+            .collect();
+
+        Ok(Self {
+            source: template.to_string(),
+            placeholders,
+            styled_literals,
+            plain_literals,
+            active_styles,
+        })
+    }
+
+    /// Исходный текст шаблона
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Места подстановки в порядке появления
+    pub fn placeholders(&self) -> &[TemplatePlaceholder] {
+        &self.placeholders
+    }
+
+    /// Форматирование лога по шаблону с применением стилевых тегов
+    pub fn render(&self, log: &Log) -> String {
+        self.render_with(log, &self.styled_literals, None)
+    }
+
+    /// Форматирование лога по шаблону без стилевых тегов
+    pub fn render_plain(&self, log: &Log) -> String {
+        self.render_with(log, &self.plain_literals, None)
+    }
+
+    /// Форматирование лога по шаблону со стилевыми тегами и цветами темы
+    pub fn render_themed(&self, log: &Log, config: &DisplayConfig) -> String {
+        self.render_with(log, &self.styled_literals, Some(config))
+    }
+
+    fn render_with(&self, log: &Log, literals: &[String], theme: Option<&DisplayConfig>) -> String {
+        let mut result = String::new();
+
+        for (index, placeholder) in self.placeholders.iter().enumerate() {
+            result.push_str(&literals[index]);
+            let value = placeholder.spec.apply(&field_value(&placeholder.field, log));
+
+            let config = match (theme, &placeholder.field) {
+                (Some(config), field) if *field != TemplateField::Message => config,
+                _ => {
+                    result.push_str(&value);
+                    continue;
+                }
+            };
+
+            if placeholder.field == TemplateField::Status {
+                let color = config.color_theme.for_status(log.status).as_str();
+                let icon = config.icons_theme.for_status(log.status);
+                result.push_str(&format!("{}{} {}{}", color, icon, value, RESET_COLOR));
+            } else {
+                result.push_str(&format!("{}{}{}", STYLE_FADED, value, RESET_COLOR));
+            }
+
+            // Восстанавливаем стиль шаблона после сброса цвета темы
+            result.push_str(&self.active_styles[index]);
+        }
+        if let Some(last) = literals.last() {
+            result.push_str(last);
+        }
+
+        result
+    }
+}
+
+impl std::str::FromStr for LogTemplate {
+    type Err = TemplateError;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        Self::parse(template)
+    }
+}
+
+/// Стилевые коды, активные в конце отформатированного текста
+fn active_style(styled: &str) -> String {
+    let tail = match styled.rfind(RESET_COLOR) {
+        Some(pos) => &styled[pos + RESET_COLOR.len()..],
+        None => styled,
+    };
+
+    tail.split('\x1b')
+        .skip(1)
+        .filter_map(|code| code.find('m').map(|end| format!("\x1b{}", &code[..=end])))
+        .collect()
+}
+
+/// Разбор содержимого `{...}`
+fn parse_placeholder(body: &str) -> Result<TemplatePlaceholder, TemplateError> {
+    let (name, spec) = match body.split_once(':') {
+        Some((name, spec)) => (name.trim(), Some(spec)),
+        None => (body.trim(), None),
+    };
+
+    // Для времени всё после `:` — строка формата strftime
+    if name == "time" {
+        let time_format = spec.unwrap_or(DEFAULT_TIME_FORMAT).to_string();
+        if StrftimeItems::new(&time_format).any(|item| matches!(item, Item::Error)) {
+            return Err(TemplateError::InvalidTimeFormat(time_format));
+        }
+        return Ok(TemplatePlaceholder {
+            field: TemplateField::Time(time_format),
+            spec: TemplateSpec::default(),
+        });
+    }
+
+    let field = match name {
+        "status" => TemplateField::Status,
+        "message" | "msg" => TemplateField::Message,
+        "component.file" | "file" => TemplateField::File,
+        "component.func" | "func" => TemplateField::Func,
+        "component.dir" | "dir" => TemplateField::Dir,
+        "component.line" | "line" => TemplateField::Line,
+        "fields" => TemplateField::Fields,
+        "thread" => TemplateField::Thread,
+        _ => return Err(TemplateError::UnknownField(name.to_string())),
+    };
+
+    let spec = match spec {
+        Some(spec) => TemplateSpec::parse(spec)?,
+        None => TemplateSpec::default(),
+    };
+
+    Ok(TemplatePlaceholder { field, spec })
+}
+
+/// Получение значения поля лога
+fn field_value(field: &TemplateField, log: &Log) -> String {
+    match field {
+        TemplateField::Time(time_format) => {
+            TimeFormat::Strftime(time_format.clone()).format(&log.time, &log.time)
+        }
+        TemplateField::Status => log.status.to_string(),
+        TemplateField::Message => log.display_message().into_owned(),
+        TemplateField::File => log.component.file_name.clone(),
+        TemplateField::Func => log.component.func_name.clone(),
+        TemplateField::Dir => log.component.dir_path.clone(),
+        TemplateField::Line => log
+            .component
+            .line
+            .map(|line| line.to_string())
+            .unwrap_or_default(),
+        TemplateField::Fields => fields_to_logfmt(&log.fields),
+        TemplateField::Thread => log.thread.clone().unwrap_or_default(),
+    }
+}
@@ -9,44 +9,216 @@
  | CopyRight: © 2025 CodeLibraty Foundation
  */
 
-use chrono::{Local, Timelike};
-use std::fs::File;
-use std::io::{self, Write};
-use std::path::Path;
+use chrono::format::{DelayedFormat, StrftimeItems};
+use chrono::{
+    DateTime, FixedOffset, Local, NaiveDate, NaiveTime, SecondsFormat, TimeDelta, TimeZone,
+    Timelike, Utc,
+};
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::hash::{Hash, Hasher};
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
 
-use crate::common_types::Status;
+use crate::colors::{RESET_COLOR, STYLE_FADED};
+use crate::common_types::{FieldValue, Status};
+use crate::log_clock::{Clock, SystemClock};
+use crate::log_file::{write_log_file, FileOpenPolicy};
+use crate::log_filter::LogFilter;
+use crate::log_formats::{escape_json, log_to_json, log_to_logfmt, logfmt_key, quote_logfmt};
+use crate::log_sink::LogSink;
+use crate::log_span::SpanEdge;
+use crate::log_summary::LogSummary;
+use crate::log_template::LogTemplate;
+use crate::theme_config::DisplayConfig;
 
 /// Время регистрации лога
-#[derive(Debug, Clone)]
+///
+/// Хранит полную метку времени: дату, наносекунды и смещение от UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct LogTime {
-    pub hour: u32,
-    pub minute: u32,
-    pub seconds: u32,
+    pub datetime: DateTime<FixedOffset>,
 }
 
 impl LogTime {
-    /// Создание нового времени лога с ручным указанием
+    /// Создание нового времени лога с ручным указанием (сегодняшняя дата, местное время)
+    ///
+    /// Значения вне диапазона — ошибка программы: в отладочной сборке это паника,
+    /// в релизной они ограничиваются (`LogTime::new(25, 0, 0)` — это 23:00:00).
+    /// Для проверки используйте `LogTime::try_new`.
     pub fn new(hour: u32, minute: u32, seconds: u32) -> Self {
-        Self {
+        debug_assert!(
+            hour < 24 && minute < 60 && seconds < 60,
+            "LogTime::new({}, {}, {}): time out of range",
             hour,
             minute,
-            seconds,
+            seconds
+        );
+        let time = NaiveTime::from_hms_opt(hour.min(23), minute.min(59), seconds.min(59));
+        Self::local_today(time.unwrap_or_default())
+    }
+
+    /// Создание времени лога с ручным указанием, `None` если время некорректно
+    pub fn try_new(hour: u32, minute: u32, seconds: u32) -> Option<Self> {
+        NaiveTime::from_hms_opt(hour, minute, seconds).map(Self::local_today)
+    }
+
+    /// Время `time` сегодняшнего дня в местном часовом поясе
+    fn local_today(time: NaiveTime) -> Self {
+        let naive = Local::now().date_naive().and_time(time);
+
+        let datetime = Local
+            .from_local_datetime(&naive)
+            .earliest()
+            // Время попало в переход на летнее время: берём первое время после перевода часов
+            .or_else(|| Local.from_local_datetime(&(naive + TimeDelta::hours(1))).earliest())
+            .unwrap_or_else(|| Local.from_utc_datetime(&naive));
+        Self::from_datetime(datetime)
+    }
+
+    /// Создание времени лога из метки времени chrono
+    pub fn from_datetime<Tz: TimeZone>(datetime: DateTime<Tz>) -> Self {
+        Self {
+            datetime: datetime.fixed_offset(),
         }
     }
 
     /// Создание нового времени лога с автоматическим определением
     pub fn now() -> Self {
-        let time = Local::now();
-        Self {
-            hour: time.hour(),
-            minute: time.minute(),
-            seconds: time.second(),
-        }
+        Self::from_datetime(Local::now())
+    }
+
+    /// Текущее время в UTC
+    pub fn now_utc() -> Self {
+        Self::from_datetime(Utc::now())
+    }
+
+    /// Разбор времени в формате RFC 3339 / ISO 8601
+    pub fn parse_rfc3339(text: &str) -> Result<Self, chrono::ParseError> {
+        DateTime::parse_from_rfc3339(text).map(Self::from_datetime)
+    }
+
+    /// То же время в UTC
+    pub fn to_utc(&self) -> Self {
+        Self::from_datetime(self.datetime.with_timezone(&Utc))
+    }
+
+    /// То же время в местном часовом поясе
+    pub fn to_local(&self) -> Self {
+        Self::from_datetime(self.datetime.with_timezone(&Local))
+    }
+
+    pub fn hour(&self) -> u32 {
+        self.datetime.hour()
+    }
+
+    pub fn minute(&self) -> u32 {
+        self.datetime.minute()
+    }
+
+    pub fn seconds(&self) -> u32 {
+        self.datetime.second()
+    }
+
+    pub fn nanoseconds(&self) -> u32 {
+        self.datetime.nanosecond()
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.datetime.date_naive()
     }
 
-    /// Форматирование времени в строку
+    /// Форматирование времени в строку `ЧЧ:ММ:СС`
     pub fn format(&self) -> String {
-        format!("{}:{}:{}", self.hour, self.minute, self.seconds)
+        self.format_with("%H:%M:%S").to_string()
+    }
+
+    /// Форматирование времени по строке формата strftime
+    pub fn format_with<'a>(&self, fmt: &'a str) -> DelayedFormat<StrftimeItems<'a>> {
+        self.datetime.format(fmt)
+    }
+
+    /// Форматирование в RFC 3339 / ISO 8601 с миллисекундами
+    pub fn to_rfc3339(&self) -> String {
+        self.datetime.to_rfc3339_opts(SecondsFormat::Millis, true)
+    }
+
+    /// Время, прошедшее с `start` (отрицательное, если `start` позже)
+    pub fn elapsed_since(&self, start: &LogTime) -> TimeDelta {
+        self.datetime.signed_duration_since(start.datetime)
+    }
+
+    /// Относительная метка времени, например `+12.345s`
+    pub fn format_relative(&self, start: &LogTime) -> String {
+        let elapsed = self.elapsed_since(start);
+        let sign = if elapsed < TimeDelta::zero() { "-" } else { "+" };
+        let elapsed = elapsed.abs();
+
+        format!(
+            "{}{}.{:03}s",
+            sign,
+            elapsed.num_seconds(),
+            elapsed.subsec_nanos() / 1_000_000
+        )
+    }
+}
+
+/// Формат времени в строках лога
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeFormat {
+    /// `14:30:45`
+    Clock,
+    /// `14:30:45.123`
+    Precise,
+    /// `2025-01-31 14:30:45.123`
+    DateTime,
+    /// `2025-01-31T14:30:45.123+03:00`
+    Rfc3339,
+    /// Время с момента создания логгера: `+12.345s`
+    Relative,
+    /// Произвольный формат strftime
+    Strftime(String),
+}
+
+impl TimeFormat {
+    /// Форматирование времени, `start` — точка отсчёта для `Relative`
+    pub fn format(&self, time: &LogTime, start: &LogTime) -> String {
+        match self {
+            TimeFormat::Clock => time.format(),
+            TimeFormat::Precise => time.format_with("%H:%M:%S%.3f").to_string(),
+            TimeFormat::DateTime => time.format_with("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            TimeFormat::Rfc3339 => time.to_rfc3339(),
+            TimeFormat::Relative => time.format_relative(start),
+            TimeFormat::Strftime(fmt) => {
+                let mut result = String::new();
+                match write!(result, "{}", time.format_with(fmt)) {
+                    Ok(()) => result,
+                    Err(_) => time.format(),
+                }
+            }
+        }
+    }
+}
+
+/// Часовой пояс, в котором логгер берёт и выводит время
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeZoneMode {
+    /// Время остаётся в поясе, в котором было записано (часы логгера дают местное)
+    AsRecorded,
+    Local,
+    Utc,
+}
+
+impl TimeZoneMode {
+    /// То же время в выбранном поясе
+    pub fn apply(&self, time: LogTime) -> LogTime {
+        match self {
+            TimeZoneMode::AsRecorded => time,
+            TimeZoneMode::Local => time.to_local(),
+            TimeZoneMode::Utc => time.to_utc(),
+        }
     }
 }
 
@@ -56,6 +228,7 @@ pub struct Component {
     pub file_name: String,
     pub func_name: String,
     pub dir_path: String,
+    pub line: Option<u32>,
 }
 
 impl Component {
@@ -65,8 +238,15 @@ impl Component {
             file_name,
             func_name,
             dir_path,
+            line: None,
         }
     }
+
+    /// Указание строки, из которой пришёл лог
+    pub fn with_line(mut self, line: u32) -> Self {
+        self.line = Some(line);
+        self
+    }
 }
 
 /// Одно сообщение лога
@@ -76,6 +256,18 @@ pub struct Log {
     pub message: String,
     pub component: Component,
     pub time: LogTime,
+    /// Структурированные поля в порядке добавления
+    pub fields: Vec<(String, FieldValue)>,
+    /// Имя или идентификатор потока, из которого пришёл лог
+    pub thread: Option<String>,
+    /// Порядковый номер лога в логгере
+    pub sequence: Option<u64>,
+    /// Сколько раз лог повторился после первого вывода
+    pub repeated: u64,
+    /// Число этапов `Logger::span`, открытых при записи лога
+    pub span_depth: usize,
+    /// Начало или конец этапа, если лог записан самим этапом
+    pub span_edge: Option<SpanEdge>,
 }
 
 impl Log {
@@ -86,48 +278,199 @@ impl Log {
             message,
             component,
             time,
+            fields: Vec::new(),
+            thread: None,
+            sequence: None,
+            repeated: 0,
+            span_depth: 0,
+            span_edge: None,
         }
     }
 
+    /// Сообщение с пометкой о повторах: `disk is full (repeated 1523 times)`
+    pub fn display_message(&self) -> Cow<'_, str> {
+        match self.repeated {
+            0 => Cow::Borrowed(&self.message),
+            1 => Cow::Owned(format!("{} (repeated 1 time)", self.message)),
+            repeated => Cow::Owned(format!("{} (repeated {} times)", self.message, repeated)),
+        }
+    }
+
+    /// Тот же статус, сообщение и компонент
+    pub fn is_repeat_of(&self, other: &Log) -> bool {
+        self.status == other.status
+            && self.message == other.message
+            && self.component.file_name == other.component.file_name
+            && self.component.func_name == other.component.func_name
+            && self.component.dir_path == other.component.dir_path
+            && self.component.line == other.component.line
+    }
+
+    /// Добавление структурированного поля
+    pub fn with_field(mut self, key: &str, value: impl Into<FieldValue>) -> Self {
+        self.fields.push((key.to_string(), value.into()));
+        self
+    }
+
+    /// Примерный размер лога в памяти в байтах
+    pub fn estimated_size(&self) -> usize {
+        let fields: usize = self
+            .fields
+            .iter()
+            .map(|(key, value)| key.len() + value.to_string().len())
+            .sum();
+
+        std::mem::size_of::<Self>()
+            + self.message.len()
+            + self.component.file_name.len()
+            + self.component.func_name.len()
+            + self.component.dir_path.len()
+            + self.thread.as_ref().map_or(0, String::len)
+            + fields
+    }
+
+    /// Значение поля по ключу
+    pub fn field(&self, key: &str) -> Option<&FieldValue> {
+        self.fields
+            .iter()
+            .find(|(field_key, _)| field_key == key)
+            .map(|(_, value)| value)
+    }
+
     /// Форматирование лога в строку
+    ///
+    /// Стилевые теги пользовательского шаблона сохраняются, цвета темы не применяются.
     pub fn format(&self, style: &LoggerPrintStyle) -> String {
+        match style {
+            LoggerPrintStyle::Custom(template) => template.render(self),
+            _ => self.render(style, None, &self.time.format()),
+        }
+    }
+
+    /// Форматирование лога в строку с цветами и иконками темы
+    pub fn format_colored(&self, style: &LoggerPrintStyle, config: &DisplayConfig) -> String {
+        self.render(style, Some(config), &self.time.format())
+    }
+
+    /// Форматирование лога, цвета применяются только при наличии конфигурации
+    fn render(&self, style: &LoggerPrintStyle, config: Option<&DisplayConfig>, time: &str) -> String {
+        let faded = |text: String| match config {
+            Some(_) => format!("{}{}{}", STYLE_FADED, text, RESET_COLOR),
+            None => text,
+        };
+        let status = match config {
+            Some(config) => format!(
+                "{}{} {}{}",
+                config.color_theme.for_status(self.status).as_str(),
+                config.icons_theme.for_status(self.status),
+                self.status,
+                RESET_COLOR
+            ),
+            None => self.status.to_string(),
+        };
+
+        // В Tiny/Flat сообщение с `=` берётся в кавычки, чтобы его не путали с полями
+        let inline_message = match self.display_message() {
+            message if message.contains('=') || message.starts_with('"') => {
+                Cow::Owned(format!("\"{}\"", escape_json(&message)))
+            }
+            message => message,
+        };
+
+        // Поля в строку для Tiny/Flat и блоком под сообщением для Full
+        let inline_fields: String = self
+            .fields
+            .iter()
+            .map(|(key, value)| {
+                format!(" {}{}", faded(format!("{}=", logfmt_key(key))), quote_logfmt(&value.to_string()))
+            })
+            .collect();
+        let key_width = self.fields.iter().map(|(key, _)| key.chars().count()).max();
+        let block_fields: String = self
+            .fields
+            .iter()
+            .map(|(key, value)| {
+                let key = format!("{:width$}", key, width = key_width.unwrap_or_default());
+                format!("\n    {} = {}", faded(key), value)
+            })
+            .collect();
+
         match style {
             LoggerPrintStyle::Tiny => {
                 format!(
-                    "{}: {} | from {}-func:{}, time is {}",
-                    self.status,
-                    self.message,
-                    self.component.file_name,
-                    self.component.func_name,
-                    self.time.format()
+                    "{}: {}{} | {}",
+                    status,
+                    inline_message,
+                    inline_fields,
+                    faded(format!(
+                        "from {}-func:{}, time is {}",
+                        self.component.file_name,
+                        self.component.func_name,
+                        time
+                    ))
                 )
             }
             LoggerPrintStyle::Flat => {
                 format!(
-                    "{}: {} | file {} | time {}",
-                    self.status,
-                    self.message,
-                    self.component.file_name,
-                    self.time.format()
+                    "{}: {}{} | {}",
+                    status,
+                    inline_message,
+                    inline_fields,
+                    faded(format!(
+                        "file {} | time {}",
+                        self.component.file_name,
+                        time
+                    ))
                 )
             }
             LoggerPrintStyle::Full => {
                 format!(
-                    "[{}|{}][{}/{}-{}]: {}",
-                    self.status,
-                    self.time.format(),
-                    self.component.dir_path,
-                    self.component.file_name,
-                    self.component.func_name,
-                    self.message
+                    "[{}|{}]{}: {}{}",
+                    status,
+                    faded(time.to_string()),
+                    faded(format!(
+                        "[{}/{}-{}]",
+                        self.component.dir_path,
+                        self.component.file_name,
+                        self.component.func_name
+                    )),
+                    self.display_message(),
+                    block_fields
                 )
             }
+            // Машиночитаемые форматы всегда без цветов и с полным временем
+            LoggerPrintStyle::Json => log_to_json(self),
+            LoggerPrintStyle::Logfmt => log_to_logfmt(self),
+            LoggerPrintStyle::Custom(template) => match config {
+                Some(config) => template.render_themed(self, config),
+                None => template.render_plain(self),
+            },
         }
     }
 }
 
+/// Имя текущего потока, а для безымянных потоков — его идентификатор
+pub fn current_thread_name() -> String {
+    let thread = std::thread::current();
+    match thread.name() {
+        Some(name) => name.to_string(),
+        None => format!("{:?}", thread.id()),
+    }
+}
+
+/// Режим цветного вывода в терминал
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Цвета только если вывод идёт в терминал и не задан `NO_COLOR`
+    Auto,
+    /// Всегда с цветами
+    Always,
+    /// Всегда без цветов
+    Never,
+}
+
 /// Стиль вывода логгера
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum LoggerPrintStyle {
     /// Стиль без символов, больше слов
     Flat,
@@ -135,15 +478,130 @@ pub enum LoggerPrintStyle {
     Tiny,
     /// Полный вывод всей информации с чётким форматированием
     Full,
+    /// Одна строка JSON на лог (JSON Lines)
+    Json,
+    /// Пары `ключ=значение` в формате logfmt
+    Logfmt,
+    /// Пользовательский шаблон, например `"{time:%H:%M:%S} {status:>7} {message}"`
+    Custom(LogTemplate),
+}
+
+/// Ограничение числа логов, хранимых в `Logger.logs`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogRetention {
+    /// Хранить все логи
+    Unbounded,
+    /// Хранить последние N логов
+    LastEntries(usize),
+    /// Хранить последние логи общим размером не больше N байт
+    LastBytes(usize),
+}
+
+/// Схлопывание повторяющихся логов с тем же статусом, сообщением и компонентом
+///
+/// Первый лог выводится сразу, повторы только подсчитываются. Когда серия
+/// повторов заканчивается, в приёмники и терминал выводится ещё одна строка
+/// с пометкой `(repeated N times)`, а лог в памяти получает счётчик `repeated`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DedupPolicy {
+    /// Выводить каждый лог
+    Off,
+    /// Схлопывать повторы, идущие подряд
+    Consecutive,
+    /// Схлопывать повторы в течение окна от первого вхождения
+    Window(TimeDelta),
+}
+
+/// Ограничение частоты логов для каждого компонента
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Сколько логов компонента пропускать за период
+    pub max_logs: u32,
+    pub per: TimeDelta,
+}
+
+/// Серия повторов одного лога
+#[derive(Debug)]
+struct RepeatRun {
+    /// Хеш статуса, сообщения и компонента для быстрого поиска серии
+    key: u64,
+    log: Log,
+    last_time: LogTime,
+}
+
+/// Хеш того, что сравнивает `Log::is_repeat_of`
+fn repeat_key(log: &Log) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    log.status.hash(&mut hasher);
+    log.message.hash(&mut hasher);
+    log.component.file_name.hash(&mut hasher);
+    log.component.func_name.hash(&mut hasher);
+    log.component.dir_path.hash(&mut hasher);
+    log.component.line.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Сколько окон ограничения частоты хранить, прежде чем удалять истёкшие
+const RATE_WINDOWS_PRUNE_AT: usize = 1024;
+
+/// Что делать с логами ниже порога важности
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterAction {
+    /// Не сохранять лог вовсе
+    Drop,
+    /// Сохранить лог в `logs`, но не выводить его
+    Suppress,
 }
 
 /// Основной класс логгера
 pub struct Logger {
+    /// Логи в памяти, при ограниченном `retention` — только последние
     pub logs: Vec<Log>,
     pub creation_time: LogTime,
     pub destruction_time: Option<LogTime>,
     pub printable_in_terminal: bool,
     pub style: LoggerPrintStyle,
+    /// Тема для цветного вывода в терминал, без неё вывод всегда простой
+    pub display_config: Option<DisplayConfig>,
+    pub color_mode: ColorMode,
+    /// Формат времени во встроенных стилях
+    pub time_format: TimeFormat,
+    /// Часовой пояс для `now()`, новых логов и вывода
+    pub time_zone: TimeZoneMode,
+    /// Минимальный статус, логи ниже него отфильтровываются
+    pub min_status: Status,
+    pub filter_action: FilterAction,
+    /// Пороги по компонентам, имеют приоритет над `min_status`
+    pub filter: Option<LogFilter>,
+    /// Как `destroy` открывает файл логов
+    pub file_policy: FileOpenPolicy,
+    /// Приёмники, получающие каждый лог сразу после добавления
+    pub sinks: Vec<Box<dyn LogSink>>,
+    /// Последняя ошибка записи в приёмники
+    pub sink_error: Option<io::Error>,
+    /// Сколько логов хранить в памяти; в приёмники попадают все логи
+    pub retention: LogRetention,
+    /// Сколько логов было вытеснено из памяти
+    pub evicted_logs: u64,
+    /// Выводить итоги работы при завершении
+    pub print_summary: bool,
+    pub dedup: DedupPolicy,
+    pub rate_limit: Option<RateLimit>,
+    /// Сколько логов было отброшено ограничением частоты
+    pub rate_limited_logs: u64,
+    /// Часы для логов без явного времени, завершения и итогов
+    pub clock: Box<dyn Clock>,
+    /// Итоги копятся по мере добавления, поэтому учитывают и вытесненные логи
+    summary: LogSummary,
+    repeats: Vec<RepeatRun>,
+    /// Число открытых этапов, см. `Logger::span`
+    pub(crate) span_depth: usize,
+    /// Начало текущего периода и число логов в нём для каждого компонента
+    rate_windows: HashMap<String, (LogTime, u32)>,
+    retained_bytes: usize,
+    next_sequence: u64,
+    /// Файл, уже записанный `finish`, и номер последнего записанного в него лога
+    written_file: Option<(PathBuf, u64)>,
 }
 
 impl Logger {
@@ -155,9 +613,173 @@ impl Logger {
             destruction_time: None,
             printable_in_terminal,
             style: LoggerPrintStyle::Tiny,
+            display_config: None,
+            color_mode: ColorMode::Auto,
+            time_format: TimeFormat::Clock,
+            time_zone: TimeZoneMode::AsRecorded,
+            min_status: Status::Trace,
+            filter_action: FilterAction::Drop,
+            filter: None,
+            file_policy: FileOpenPolicy::Append,
+            sinks: Vec::new(),
+            sink_error: None,
+            retention: LogRetention::Unbounded,
+            evicted_logs: 0,
+            print_summary: false,
+            dedup: DedupPolicy::Off,
+            rate_limit: None,
+            rate_limited_logs: 0,
+            clock: Box::new(SystemClock),
+            summary: LogSummary::new(creation_time),
+            repeats: Vec::new(),
+            span_depth: 0,
+            rate_windows: HashMap::new(),
+            retained_bytes: 0,
+            next_sequence: 0,
+            written_file: None,
         }
     }
 
+    /// Вытеснение старых логов сверх ограничения `retention`
+    fn enforce_retention(&mut self) {
+        let excess = match self.retention {
+            LogRetention::Unbounded => 0,
+            LogRetention::LastEntries(count) => self.logs.len().saturating_sub(count),
+            // Последний лог храним, даже если он один больше лимита
+            LogRetention::LastBytes(bytes) => {
+                let mut retained = self.retained_bytes;
+                let mut excess = 0;
+                while retained > bytes && excess + 1 < self.logs.len() {
+                    retained = retained.saturating_sub(self.logs[excess].estimated_size());
+                    excess += 1;
+                }
+                excess
+            }
+        };
+        if excess == 0 {
+            return;
+        }
+
+        for log in self.logs.drain(..excess) {
+            self.retained_bytes = self.retained_bytes.saturating_sub(log.estimated_size());
+        }
+        self.evicted_logs += excess as u64;
+    }
+
+    /// Последние `count` логов из памяти, от старых к новым
+    pub fn recent(&self, count: usize) -> impl Iterator<Item = &Log> {
+        self.logs.iter().skip(self.logs.len().saturating_sub(count))
+    }
+
+    /// Последние `count` логов строками в стиле логгера, например для отчёта о падении
+    pub fn dump_recent(&self, count: usize) -> String {
+        self.recent(count)
+            .map(|log| self.format_log(log))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Добавление приёмника логов
+    pub fn add_sink(&mut self, sink: impl LogSink + 'static) {
+        self.sinks.push(Box::new(sink));
+    }
+
+    /// Запись лога во все приёмники
+    fn write_to_sinks(&mut self, log: &Log) {
+        if self.sinks.is_empty() {
+            return;
+        }
+
+        let line = self.format_log(log);
+        for sink in &mut self.sinks {
+            if let Err(error) = sink.write_log(log, &line) {
+                self.sink_error = Some(error);
+            }
+        }
+    }
+
+    /// Сброс буферов всех приёмников, возвращает первую ошибку
+    pub fn flush_sinks(&mut self) -> io::Result<()> {
+        let mut result = Ok(());
+        for sink in &mut self.sinks {
+            if let Err(error) = sink.flush() {
+                if result.is_ok() {
+                    result = Err(error);
+                }
+            }
+        }
+        result
+    }
+
+    /// Проходит ли лог с этим статусом порог важности для компонента
+    pub fn is_enabled(&self, status: Status, component: &Component) -> bool {
+        let min_status = self
+            .filter
+            .as_ref()
+            .and_then(|filter| filter.status_for(component))
+            .unwrap_or(self.min_status);
+
+        status.severity() >= min_status.severity()
+    }
+
+    /// Тема, которую следует применять при выводе в терминал
+    pub fn terminal_display_config(&self) -> Option<&DisplayConfig> {
+        if self.terminal_colors() {
+            self.display_config.as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// Разрешены ли цвета в терминале по `color_mode`
+    fn terminal_colors(&self) -> bool {
+        match self.color_mode {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => {
+                std::env::var_os("NO_COLOR").is_none() && io::stdout().is_terminal()
+            }
+        }
+    }
+
+    /// Форматирование лога для вывода в файл
+    pub fn format_log(&self, log: &Log) -> String {
+        log.render(&self.style, None, &self.format_time(&log.time))
+    }
+
+    /// Форматирование лога для вывода в терминал
+    ///
+    /// В текстовых стилях сообщения внутри этапов получают отступ, см. `Logger::span`.
+    pub fn format_for_terminal(&self, log: &Log) -> String {
+        let indented;
+        let log = match &self.style {
+            LoggerPrintStyle::Json | LoggerPrintStyle::Logfmt => log,
+            _ => match log.span_message() {
+                Cow::Borrowed(_) => log,
+                Cow::Owned(message) => {
+                    indented = Log {
+                        message,
+                        ..log.clone()
+                    };
+                    &indented
+                }
+            },
+        };
+
+        match (&self.style, self.terminal_display_config()) {
+            // Теги шаблона работают и без темы, если цвета не отключены
+            (LoggerPrintStyle::Custom(template), None) if self.terminal_colors() => {
+                template.render(log)
+            }
+            (style, config) => log.render(style, config, &self.format_time(&log.time)),
+        }
+    }
+
+    /// Форматирование времени лога по формату логгера
+    pub fn format_time(&self, time: &LogTime) -> String {
+        self.time_format.format(&self.time_zone.apply(*time), &self.creation_time)
+    }
+
     /// Добавление лога в логгер
     pub fn add_log(
         &mut self,
@@ -166,17 +788,184 @@ impl Logger {
         status: Status,
         time: Option<LogTime>,
     ) -> Option<String> {
-        let time = time.unwrap_or_else(LogTime::now);
-        let log = Log::new(status, message, component, time);
+        self.add_log_with_fields(message, component, status, time, Vec::new())
+    }
 
-        let formatted = if self.printable_in_terminal {
-            Some(log.format(&self.style))
-        } else {
-            None
-        };
+    /// Добавление лога со структурированными полями
+    pub fn add_log_with_fields(
+        &mut self,
+        message: String,
+        component: Component,
+        status: Status,
+        time: Option<LogTime>,
+        fields: Vec<(String, FieldValue)>,
+    ) -> Option<String> {
+        let time = time.unwrap_or_else(|| self.now());
+        let mut log = Log::new(status, message, component, time);
+        log.fields = fields;
+        self.add_entry(log)
+    }
+
+    /// Добавление готового лога, логгер дописывает поток и порядковый номер
+    ///
+    /// Возвращает строки для терминала: сам лог и завершённые серии повторов.
+    pub fn add_entry(&mut self, mut log: Log) -> Option<String> {
+        let enabled = self.is_enabled(log.status, &log.component);
+        if !enabled && self.filter_action == FilterAction::Drop {
+            return None;
+        }
 
+        log.thread = Some(current_thread_name());
+        log.time = self.time_zone.apply(log.time);
+
+        // Повторы не расходуют лимит частоты
+        let mut output = Vec::new();
+        if enabled {
+            output = self.close_repeats(Some(&log));
+            if self.count_repeat(&log) {
+                self.summary.record(&log);
+                return join_lines(output);
+            }
+            if self.exceeds_rate_limit(&log) {
+                self.rate_limited_logs += 1;
+                return join_lines(output);
+            }
+        }
+
+        self.store_entry(log, enabled, output)
+    }
+
+    /// Добавление лога в обход фильтра, повторов и ограничения частоты
+    ///
+    /// Для логов, которые нельзя потерять, например о панике.
+    pub(crate) fn add_entry_unfiltered(&mut self, mut log: Log) -> Option<String> {
+        log.thread = Some(current_thread_name());
+        log.time = self.time_zone.apply(log.time);
+
+        let output = self.close_repeats(None);
+        self.store_entry(log, true, output)
+    }
+
+    /// Нумерация, вывод, запись в приёмники и сохранение прошедшего проверки лога
+    fn store_entry(&mut self, mut log: Log, enabled: bool, mut output: Vec<String>) -> Option<String> {
+        log.span_depth = self.span_depth;
+        log.sequence = Some(self.next_sequence);
+        self.next_sequence += 1;
+
+        if self.printable_in_terminal && enabled {
+            output.push(self.format_for_terminal(&log));
+        }
+
+        if enabled {
+            self.write_to_sinks(&log);
+            self.summary.record(&log);
+            if self.dedup != DedupPolicy::Off {
+                self.repeats.push(RepeatRun {
+                    key: repeat_key(&log),
+                    log: log.clone(),
+                    last_time: log.time,
+                });
+            }
+        }
+
+        self.retained_bytes += log.estimated_size();
         self.logs.push(log);
-        formatted
+        self.enforce_retention();
+        join_lines(output)
+    }
+
+    /// Завершение всех серий повторов, возвращает строки для терминала
+    pub fn flush_repeats(&mut self) -> Option<String> {
+        let output = self.close_repeats(None);
+        join_lines(output)
+    }
+
+    /// Учёт лога как повтора одной из открытых серий
+    fn count_repeat(&mut self, log: &Log) -> bool {
+        let key = repeat_key(log);
+        let Some(run) = self
+            .repeats
+            .iter_mut()
+            .find(|run| run.key == key && log.is_repeat_of(&run.log))
+        else {
+            return false;
+        };
+        run.log.repeated += 1;
+        run.last_time = log.time;
+
+        // Логи в памяти упорядочены по номеру, нужный мог быть уже вытеснен
+        let (sequence, repeated) = (run.log.sequence, run.log.repeated);
+        if let Ok(index) = self.logs.binary_search_by_key(&sequence, |stored| stored.sequence) {
+            let stored = &mut self.logs[index];
+            // Размер считается заново, чтобы вытеснение вычитало то же, что было учтено
+            let before = stored.estimated_size();
+            stored.repeated = repeated;
+            self.retained_bytes = self.retained_bytes.saturating_sub(before) + stored.estimated_size();
+        }
+        true
+    }
+
+    /// Закрытие серий, которые не продолжит лог `next` (без `next` — всех серий)
+    fn close_repeats(&mut self, next: Option<&Log>) -> Vec<String> {
+        let dedup = self.dedup;
+        let (closed, open): (Vec<RepeatRun>, Vec<RepeatRun>) =
+            std::mem::take(&mut self.repeats)
+                .into_iter()
+                .partition(|run| match (next, dedup) {
+                    (None, _) | (_, DedupPolicy::Off) => true,
+                    (Some(next), DedupPolicy::Consecutive) => !next.is_repeat_of(&run.log),
+                    (Some(next), DedupPolicy::Window(window)) => {
+                        next.time.elapsed_since(&run.log.time) > window
+                    }
+                });
+        self.repeats = open;
+
+        let mut output = Vec::new();
+        for run in closed.into_iter().filter(|run| run.log.repeated > 0) {
+            let mut notice = run.log;
+            notice.time = run.last_time;
+            notice.sequence = Some(self.next_sequence);
+            self.next_sequence += 1;
+            self.write_to_sinks(&notice);
+            if self.printable_in_terminal {
+                output.push(self.format_for_terminal(&notice));
+            }
+        }
+        output
+    }
+
+    /// Превышает ли лог ограничение частоты для своего компонента
+    fn exceeds_rate_limit(&mut self, log: &Log) -> bool {
+        let Some(limit) = self.rate_limit else {
+            return false;
+        };
+
+        let key = format!(
+            "{}/{}:{}",
+            log.component.dir_path.trim_end_matches('/'),
+            log.component.file_name,
+            log.component.func_name
+        );
+        if self.rate_windows.len() >= RATE_WINDOWS_PRUNE_AT && !self.rate_windows.contains_key(&key) {
+            // Окна с истёкшим периодом больше ничего не ограничивают
+            self.rate_windows
+                .retain(|_, (start, _)| log.time.elapsed_since(start) < limit.per);
+        }
+        let (start, count) = self.rate_windows.entry(key).or_insert((log.time, 0));
+        if log.time.elapsed_since(start) >= limit.per || log.time < *start {
+            *start = log.time;
+            *count = 0;
+        }
+
+        *count += 1;
+        *count > limit.max_logs
+    }
+
+    /// Итоги по всем выведенным логам на текущий момент
+    pub fn summary(&self) -> LogSummary {
+        let mut summary = self.summary.clone();
+        summary.end = self.destruction_time.unwrap_or_else(|| self.now());
+        summary
     }
 
     /// Уничтожение экземпляра логгера
@@ -185,31 +974,84 @@ impl Logger {
         file_for_logs: &str,
         write_to_file: bool,
         print_everything_now: bool,
-    ) -> io::Result<()> {
-        self.destruction_time = Some(LogTime::now());
+    ) -> io::Result<LogSummary> {
+        self.finish(file_for_logs, write_to_file, print_everything_now)
+    }
+
+    /// Завершение работы логгера без его уничтожения: вывод, запись в файл и сброс приёмников
+    ///
+    /// Повторный вызов с тем же файлом дописывает в него только новые логи.
+    /// Возвращает итоги работы, см. `Logger::summary`
+    pub fn finish(
+        &mut self,
+        file_for_logs: &str,
+        write_to_file: bool,
+        print_everything_now: bool,
+    ) -> io::Result<LogSummary> {
+        self.destruction_time = Some(self.now());
 
-        let formatted_logs: Vec<String> = self
+        let visible_logs: Vec<&Log> = self
             .logs
             .iter()
-            .map(|log| log.format(&self.style))
+            .filter(|log| self.is_enabled(log.status, &log.component))
             .collect();
 
         if print_everything_now {
-            for log in &formatted_logs {
-                println!("{}", log);
+            for log in &visible_logs {
+                println!("{}", self.format_for_terminal(log));
             }
         }
 
         if write_to_file {
-            // Создаём файл если не существует
-            if !Path::new(file_for_logs).exists() {
-                File::create(file_for_logs)?;
+            let path = Path::new(file_for_logs);
+            // Логи, уже записанные в этот файл, например обработчиком паник, не повторяются
+            let written_through = match &self.written_file {
+                Some((written, sequence)) if written == path => Some(*sequence),
+                _ => None,
+            };
+            let policy = match written_through {
+                Some(_) => FileOpenPolicy::Append,
+                None => self.file_policy,
+            };
+
+            // В файл всегда пишем без цветов
+            let formatted_logs: Vec<String> = visible_logs
+                .iter()
+                .filter(|log| match (written_through, log.sequence) {
+                    (Some(written), Some(sequence)) => sequence > written,
+                    _ => true,
+                })
+                .map(|log| self.format_log(log))
+                .collect();
+            write_log_file(path, policy, &formatted_logs)?;
+
+            let last_sequence = self.logs.iter().filter_map(|log| log.sequence).max();
+            if let Some(sequence) = last_sequence.or(written_through) {
+                self.written_file = Some((path.to_path_buf(), sequence));
             }
+        }
 
-            let mut file = File::create(file_for_logs)?;
-            file.write_all(formatted_logs.join("\n").as_bytes())?;
+        let repeats = self.flush_repeats();
+        if let Some(repeats) = repeats.filter(|_| !print_everything_now) {
+            println!("{}", repeats);
         }
 
-        Ok(())
+        self.flush_sinks()?;
+
+        let summary = self.summary();
+        if self.print_summary {
+            println!("{}", summary.render(self.terminal_display_config()));
+        }
+
+        Ok(summary)
+    }
+}
+
+/// Объединение строк для терминала в одну
+fn join_lines(lines: Vec<String>) -> Option<String> {
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,137 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/panic_hook.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+use std::backtrace::Backtrace;
+use std::panic::{self, PanicHookInfo};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::common_types::{FieldValue, Status};
+use crate::logger::{Component, Log, LogTime};
+use crate::shared_logger::SharedLogger;
+
+/// Сколько ждать логгер, занятый другим потоком, прежде чем сдаться
+const LOCK_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Обработчик паник, записывающий панику в логгер как `Status::Fatal`
+///
+/// Лог получает место паники как компонент (`file`, `line`), сообщение паники
+/// и, по желанию, поле `backtrace`. Фильтры, повторы и ограничение частоты
+/// к нему не применяются. После записи все приёмники сбрасываются.
+#[derive(Debug, Clone, Default)]
+pub struct PanicHook {
+    /// Добавлять стек вызовов в поле `backtrace`
+    pub backtrace: bool,
+    /// Записать все логи в этот файл, как это сделал бы `destroy`
+    pub log_file: Option<PathBuf>,
+    /// Вызывать и предыдущий обработчик (по умолчанию — стандартный вывод паники)
+    pub chain: bool,
+}
+
+impl PanicHook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn backtrace(mut self, enabled: bool) -> Self {
+        self.backtrace = enabled;
+        self
+    }
+
+    pub fn log_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.log_file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn chain(mut self, enabled: bool) -> Self {
+        self.chain = enabled;
+        self
+    }
+
+    /// Установка обработчика для всего процесса
+    pub fn install(self, logger: &SharedLogger) {
+        let logger = logger.clone();
+        let previous = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            let logged = self.record(&logger, info);
+            if self.chain || !logged {
+                previous(info);
+            }
+        }));
+    }
+
+    /// Запись паники, `false` — логгер так и не освободился
+    fn record(&self, logger: &SharedLogger, info: &PanicHookInfo<'_>) -> bool {
+        // Паника могла случиться, пока этот же поток держит логгер
+        let started = Instant::now();
+        let mut logger = loop {
+            if let Some(logger) = logger.try_lock() {
+                break logger;
+            }
+            if started.elapsed() >= LOCK_TIMEOUT {
+                return false;
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+
+        // Паника выводится всегда, даже если логгер не печатает в терминал
+        let log = panic_log(info, self.backtrace, logger.now());
+        let output = logger
+            .add_entry_unfiltered(log)
+            .or_else(|| logger.logs.last().map(|log| logger.format_for_terminal(log)));
+        if let Some(output) = output {
+            eprintln!("{}", output);
+        }
+        if let Some(path) = &self.log_file {
+            let _ = logger.finish(&path.to_string_lossy(), true, false);
+        } else {
+            let _ = logger.flush_sinks();
+        }
+        true
+    }
+}
+
+/// Лог с сообщением и местом паники на момент `time`
+pub fn panic_log(info: &PanicHookInfo<'_>, backtrace: bool, time: LogTime) -> Log {
+    let payload = info.payload();
+    let message = match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        _ => "Box<dyn Any>".to_string(),
+    };
+
+    let component = match info.location() {
+        Some(location) => {
+            let path = Path::new(location.file());
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| location.file().to_string());
+            let dir_path = path
+                .parent()
+                .map(|dir| dir.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            Component::new(file_name, "panic".to_string(), dir_path).with_line(location.line())
+        }
+        None => Component::new("<unknown>".to_string(), "panic".to_string(), String::new()),
+    };
+
+    let mut log = Log::new(Status::Fatal, message, component, time);
+    if backtrace {
+        log.fields.push((
+            "backtrace".to_string(),
+            FieldValue::Str(Backtrace::force_capture().to_string()),
+        ));
+    }
+    log
+}
@@ -0,0 +1,105 @@
+/*
+ | PrettyTerm - Pretty Terminal Printers
+ | =====================================
+ | Description: Make your terminal interfaces prettier!
+ | File:        src/shared_logger.rs
+ | Repository:  https://github.com/CodeLibraty/prettyterm-rs
+ |
+ | SPDX-License-Identifier: GPL-3.0-or-later
+ | CopyRight: © 2025 CodeLibraty Foundation
+ */
+
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard, TryLockError};
+
+use crate::common_types::{FieldValue, Status};
+use crate::log_summary::LogSummary;
+use crate::logger::{Component, Log, LogTime, Logger};
+
+/// Потокобезопасная ссылка на логгер
+///
+/// Клонирование дешёвое: все клоны пишут в один и тот же `Logger`,
+/// каждый лог добавляется и записывается в приёмники целиком под блокировкой.
+#[derive(Clone)]
+pub struct SharedLogger {
+    inner: Arc<Mutex<Logger>>,
+}
+
+impl SharedLogger {
+    /// Создание общей ссылки на логгер
+    pub fn new(logger: Logger) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(logger)),
+        }
+    }
+
+    /// Блокировка логгера для прямого доступа
+    ///
+    /// Паника в другом потоке не делает логгер недоступным.
+    pub fn lock(&self) -> MutexGuard<'_, Logger> {
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Блокировка логгера без ожидания, `None` если он занят
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, Logger>> {
+        match self.inner.try_lock() {
+            Ok(logger) => Some(logger),
+            Err(TryLockError::Poisoned(poisoned)) => Some(poisoned.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+
+    /// Добавление лога в логгер
+    pub fn add_log(
+        &self,
+        message: String,
+        component: Component,
+        status: Status,
+        time: Option<LogTime>,
+    ) -> Option<String> {
+        self.lock().add_log(message, component, status, time)
+    }
+
+    /// Добавление лога со структурированными полями
+    pub fn add_log_with_fields(
+        &self,
+        message: String,
+        component: Component,
+        status: Status,
+        time: Option<LogTime>,
+        fields: Vec<(String, FieldValue)>,
+    ) -> Option<String> {
+        self.lock()
+            .add_log_with_fields(message, component, status, time, fields)
+    }
+
+    /// Добавление готового лога
+    pub fn add_entry(&self, log: Log) -> Option<String> {
+        self.lock().add_entry(log)
+    }
+
+    /// Завершение работы общего логгера, см. `Logger::finish`
+    pub fn finish(
+        &self,
+        file_for_logs: &str,
+        write_to_file: bool,
+        print_everything_now: bool,
+    ) -> io::Result<LogSummary> {
+        self.lock()
+            .finish(file_for_logs, write_to_file, print_everything_now)
+    }
+
+    /// Извлечение логгера, если это последняя ссылка на него
+    pub fn try_into_inner(self) -> Result<Logger, Self> {
+        match Arc::try_unwrap(self.inner) {
+            Ok(mutex) => Ok(mutex.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner())),
+            Err(inner) => Err(Self { inner }),
+        }
+    }
+}
+
+impl From<Logger> for SharedLogger {
+    fn from(logger: Logger) -> Self {
+        Self::new(logger)
+    }
+}
@@ -70,6 +70,27 @@ pub fn process_style_tags(text: &str) -> String {
     result
 }
 
+/// Удаление стилевых тегов без применения стилей
+pub fn strip_style_tags(text: &str) -> String {
+    let mut result = String::new();
+    let mut chars = text.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch == '<' {
+            // Пропускаем тег целиком
+            for next_ch in chars.by_ref() {
+                if next_ch == '>' {
+                    break;
+                }
+            }
+        } else {
+            result.push(ch);
+        }
+    }
+
+    result
+}
+
 /// Получение кода стиля по имени
 fn get_style_code(style: &str) -> &'static str {
     match style.to_lowercase().as_str() {
@@ -10,6 +10,7 @@
  */
 
 use crate::colors::*;
+use crate::common_types::Status;
 
 #[derive(Debug, Clone, Copy)]
 pub enum TerminalColors {
@@ -60,6 +61,16 @@ impl ColorTheme {
             warning_color,
         }
     }
+
+    /// Цвет для статуса лога
+    pub fn for_status(&self, status: Status) -> TerminalColors {
+        match status {
+            Status::Ok => self.success_color,
+            Status::Error | Status::Fatal => self.error_color,
+            Status::Warn => self.warning_color,
+            Status::Info | Status::Trace | Status::Debug | Status::Custom(_) => self.hint_color,
+        }
+    }
 }
 
 impl Default for ColorTheme {
@@ -95,6 +106,16 @@ impl IconsTheme {
             warning_icon,
         }
     }
+
+    /// Иконка для статуса лога
+    pub fn for_status(&self, status: Status) -> &str {
+        match status {
+            Status::Ok => &self.success_icon,
+            Status::Error | Status::Fatal => &self.error_icon,
+            Status::Warn => &self.warning_icon,
+            Status::Info | Status::Trace | Status::Debug | Status::Custom(_) => &self.hint_icon,
+        }
+    }
 }
 
 impl Default for IconsTheme {
@@ -25,22 +25,88 @@ fn test_status_display() {
     assert_eq!(Status::Warn.to_string(), "Warning");
 }
 
+#[test]
+fn test_status_ordering() {
+    assert!(Status::Trace < Status::Debug);
+    assert!(Status::Debug < Status::Info);
+    assert!(Status::Info < Status::Ok);
+    assert!(Status::Ok < Status::Warn);
+    assert!(Status::Warn < Status::Error);
+    assert!(Status::Error < Status::Fatal);
+    assert_eq!(Status::Custom("Note").severity(), Status::Info.severity());
+    assert!(Status::Info < Status::Custom("Note"));
+    assert_eq!(Status::Custom("Note").to_string(), "Note");
+    assert_eq!("WARNING".parse::<Status>(), Ok(Status::Warn));
+    assert_eq!("debug".parse::<Status>(), Ok(Status::Debug));
+    assert!("verbose".parse::<Status>().is_err());
+}
+
 #[test]
 fn test_log_time_creation() {
     let time = LogTime::new(14, 30, 45);
-    assert_eq!(time.hour, 14);
-    assert_eq!(time.minute, 30);
-    assert_eq!(time.seconds, 45);
+    assert_eq!(time.hour(), 14);
+    assert_eq!(time.minute(), 30);
+    assert_eq!(time.seconds(), 45);
     assert_eq!(time.format(), "14:30:45");
+
+    assert!(LogTime::try_new(24, 0, 0).is_none());
+    assert_eq!(LogTime::try_new(9, 5, 3).unwrap().format(), "09:05:03");
+    // Вне диапазона: паника в отладочной сборке, ограничение в релизной
+    let clamped = std::panic::catch_unwind(|| LogTime::new(25, 61, 99));
+    assert_eq!(clamped.is_err(), cfg!(debug_assertions));
+    if let Ok(time) = clamped {
+        assert_eq!(time.format(), "23:59:59");
+    }
 }
 
 #[test]
 fn test_log_time_now() {
     let time = LogTime::now();
     // Проверяем что время валидное
-    assert!(time.hour < 24);
-    assert!(time.minute < 60);
-    assert!(time.seconds < 60);
+    assert!(time.hour() < 24);
+    assert!(time.minute() < 60);
+    assert!(time.seconds() < 60);
+}
+
+#[test]
+fn test_log_time_padding_and_formats() {
+    let time = LogTime::parse_rfc3339("2025-03-07T09:05:03.042+03:00").unwrap();
+    assert_eq!(time.format(), "09:05:03");
+    assert_eq!(time.nanoseconds(), 42_000_000);
+    assert_eq!(time.date().to_string(), "2025-03-07");
+    assert_eq!(time.format_with("%Y/%m/%d %H:%M:%S%.3f").to_string(), "2025/03/07 09:05:03.042");
+    assert_eq!(time.to_rfc3339(), "2025-03-07T09:05:03.042+03:00");
+    assert_eq!(time.to_utc().to_rfc3339(), "2025-03-07T06:05:03.042Z");
+    assert_eq!(time.to_utc(), time);
+
+    let start = LogTime::parse_rfc3339("2025-03-07T09:04:00+03:00").unwrap();
+    assert_eq!(time.format_relative(&start), "+63.042s");
+    assert_eq!(TimeFormat::DateTime.format(&time, &start), "2025-03-07 09:05:03.042");
+    assert!(start < time);
+}
+
+#[test]
+fn test_logger_time_format() {
+    let start = LogTime::parse_rfc3339("2025-03-07T23:59:59+00:00").unwrap();
+    let mut logger = Logger::new(start, true);
+    let component = Component::new("test.rs".to_string(), "main".to_string(), "/src".to_string());
+    let time = LogTime::parse_rfc3339("2025-03-08T00:00:01.5+00:00").unwrap();
+
+    logger.time_format = TimeFormat::Rfc3339;
+    let line = logger.add_log("late".to_string(), component.clone(), Status::Info, Some(time));
+    assert!(line.unwrap().contains("time is 2025-03-08T00:00:01.500Z"));
+
+    logger.time_format = TimeFormat::Relative;
+    let line = logger.add_log("late".to_string(), component.clone(), Status::Info, Some(time));
+    assert!(line.unwrap().contains("time is +2.500s"));
+
+    logger.time_format = TimeFormat::Rfc3339;
+    logger.time_zone = TimeZoneMode::Utc;
+    let moscow = LogTime::parse_rfc3339("2025-03-08T03:00:01.5+03:00").unwrap();
+    let line = logger.add_log("late".to_string(), component, Status::Info, Some(moscow));
+    assert!(line.unwrap().contains("time is 2025-03-08T00:00:01.500Z"));
+    assert_eq!(logger.recent(1).next().unwrap().time.to_rfc3339(), "2025-03-08T00:00:01.500Z");
+    assert_eq!(logger.now().datetime.offset().local_minus_utc(), 0);
 }
 
 #[test]
@@ -94,6 +160,186 @@ fn test_log_format_full() {
     assert!(formatted.contains("warning"));
 }
 
+#[test]
+fn test_log_format_custom_template() {
+    let time = LogTime::new(9, 5, 3);
+    let component = Component::new("parser.rs".to_string(), "parse".to_string(), "/src".to_string())
+        .with_line(42);
+    let log = Log::new(Status::Warn, "deprecated syntax".to_string(), component, time);
+
+    let template = LogTemplate::parse(
+        "{time:%H:%M:%S} {status:>7} {component.file}:{component.line} {message}",
+    )
+    .unwrap();
+    let formatted = log.format(&LoggerPrintStyle::Custom(template));
+    assert_eq!(formatted, "09:05:03 Warning parser.rs:42 deprecated syntax");
+}
+
+#[test]
+fn test_log_template_spec_and_markup() {
+    let time = LogTime::new(10, 20, 30);
+    let component = Component::new("file.rs".to_string(), "func".to_string(), "/src".to_string());
+    let log = Log::new(Status::Ok, "done".to_string(), component, time);
+
+    let template = LogTemplate::parse("<green>[{status:*^6}]</green> {{{message:.2}}}").unwrap();
+    assert_eq!(template.render_plain(&log), "[**Ok**] {do}");
+
+    let styled = template.render(&log);
+    assert!(styled.contains("\x1b[32m"));
+    assert!(styled.contains("[**Ok**]"));
+
+    // Теги остаются в `Log::format` и терминале, но не в файле и не при отключённых цветах
+    let style = LoggerPrintStyle::Custom(template);
+    assert_eq!(log.format(&style), styled);
+    let mut logger = Logger::new(time, false);
+    logger.style = style;
+    logger.color_mode = ColorMode::Always;
+    assert_eq!(logger.format_for_terminal(&log), styled);
+    assert_eq!(logger.format_log(&log), "[**Ok**] {do}");
+    logger.color_mode = ColorMode::Never;
+    assert_eq!(logger.format_for_terminal(&log), "[**Ok**] {do}");
+}
+
+#[test]
+fn test_log_template_errors() {
+    assert_eq!(
+        LogTemplate::parse("{unknown}"),
+        Err(TemplateError::UnknownField("unknown".to_string()))
+    );
+    assert_eq!(LogTemplate::parse("{status"), Err(TemplateError::UnclosedPlaceholder(0)));
+    assert!(matches!(LogTemplate::parse("{status:>x}"), Err(TemplateError::InvalidSpec(_))));
+    assert!(matches!(LogTemplate::parse("<{status}>"), Err(TemplateError::PlaceholderInsideTag)));
+}
+
+#[test]
+fn test_log_format_json() {
+    let time = LogTime::parse_rfc3339("2025-03-07T09:05:03.042Z").unwrap();
+    let component = Component::new("parser.rs".to_string(), "parse".to_string(), "src\\win".to_string())
+        .with_line(7);
+    let log = Log::new(Status::Error, "bad \"token\"\n\tnext".to_string(), component, time);
+
+    assert_eq!(
+        log.format(&LoggerPrintStyle::Json),
+        r#"{"time":"2025-03-07T09:05:03.042Z","status":"Error","message":"bad \"token\"\n\tnext","component":{"file":"parser.rs","func":"parse","dir":"src\\win","line":7}}"#
+    );
+    assert_eq!(escape_json("\u{1}"), "\\u0001");
+}
+
+#[test]
+fn test_log_format_logfmt() {
+    let time = LogTime::parse_rfc3339("2025-03-07T09:05:03Z").unwrap();
+    let component = Component::new("parser.rs".to_string(), "parse".to_string(), String::new());
+    let log = Log::new(Status::Warn, "a=b \"c\"".to_string(), component, time);
+
+    assert_eq!(
+        log.format(&LoggerPrintStyle::Logfmt),
+        r#"time=2025-03-07T09:05:03.000Z status=Warning msg="a=b \"c\"" file=parser.rs func=parse dir="""#
+    );
+
+    // Ключи полей очищаются, встроенные имена получают префикс
+    let log = log
+        .with_field("build id", 7)
+        .with_field("a=b", true)
+        .with_field("msg", "user")
+        .with_field("field.x", 1);
+    let line = log.format(&LoggerPrintStyle::Logfmt);
+    assert!(line.ends_with(r#"dir="" build_id=7 a_b=true field.msg=user field.field.x=1"#));
+
+    let parsed = LogParser::new(LoggerPrintStyle::Logfmt).parse_entry(&line).unwrap();
+    assert_eq!(parsed.message, "a=b \"c\"");
+    let keys: Vec<&str> = parsed.fields.iter().map(|(key, _)| key.as_str()).collect();
+    assert_eq!(keys, vec!["build_id", "a_b", "msg", "field.x"]);
+}
+
+#[test]
+fn test_log_fields_formats() {
+    let time = LogTime::parse_rfc3339("2025-03-07T09:05:03Z").unwrap();
+    let component = Component::new("build.rs".to_string(), "run".to_string(), "src".to_string());
+    let log = Log::new(Status::Ok, "compiled".to_string(), component, time)
+        .with_field("file", std::path::Path::new("src/main.rs"))
+        .with_field("duration_ms", 42)
+        .with_field("target", "x86 64")
+        .with_field("ratio", 1.0);
+
+    assert_eq!(log.field("duration_ms"), Some(&FieldValue::Int(42)));
+    assert!(log
+        .format(&LoggerPrintStyle::Tiny)
+        .starts_with(r#"Ok: compiled file=src/main.rs duration_ms=42 target="x86 64" ratio=1 | from"#));
+    assert!(log.format(&LoggerPrintStyle::Full).ends_with(
+        ": compiled\n    file        = src/main.rs\n    duration_ms = 42\n    target      = x86 64\n    ratio       = 1"
+    ));
+    assert!(log
+        .format(&LoggerPrintStyle::Json)
+        .ends_with(r#","fields":{"file":"src/main.rs","duration_ms":42,"target":"x86 64","ratio":1.0}}"#));
+    assert!(log
+        .format(&LoggerPrintStyle::Logfmt)
+        .ends_with(r#"dir=src field.file=src/main.rs duration_ms=42 target="x86 64" ratio=1"#));
+
+    let template = LogTemplate::parse("{message} [{fields}]").unwrap();
+    assert_eq!(
+        log.format(&LoggerPrintStyle::Custom(template)),
+        r#"compiled [file=src/main.rs duration_ms=42 target="x86 64" ratio=1]"#
+    );
+}
+
+#[test]
+fn test_logger_add_log_with_fields() {
+    let mut logger = Logger::new(LogTime::now(), true);
+    let component = Component::new("test.rs".to_string(), "main".to_string(), "/src".to_string());
+
+    let line = logger.add_log_with_fields(
+        "done".to_string(),
+        component,
+        Status::Ok,
+        None,
+        fields!("count" => 3u32, "cached" => true),
+    );
+
+    assert!(line.unwrap().starts_with("Ok: done count=3 cached=true |"));
+    assert_eq!(logger.logs[0].fields.len(), 2);
+    assert_eq!(logger.logs[0].field("count"), Some(&FieldValue::UInt(3)));
+}
+
+#[test]
+fn test_log_format_colored() {
+    let time = LogTime::new(10, 20, 30);
+    let component = Component::new("file.rs".to_string(), "func".to_string(), "/src".to_string());
+    let log = Log::new(Status::Error, "failed".to_string(), component, time);
+
+    let formatted = log.format_colored(&LoggerPrintStyle::Full, &DisplayConfig::default());
+    assert!(formatted.contains(&format!("{}✗ Error{}", FG_RED, RESET_COLOR)));
+    assert!(formatted.contains(&format!("{}10:20:30{}", STYLE_FADED, RESET_COLOR)));
+    assert!(formatted.ends_with(": failed"));
+
+    let template = LogTemplate::parse("<bold>{status} {message}</bold>").unwrap();
+    let formatted = log.format_colored(&LoggerPrintStyle::Custom(template), &DisplayConfig::default());
+    assert!(formatted.contains(&format!("{}✗ Error{}{}", FG_RED, RESET_COLOR, STYLE_BOLD)));
+}
+
+#[test]
+fn test_logger_color_mode() {
+    let component = Component::new("file.rs".to_string(), "func".to_string(), "/src".to_string());
+    let mut logger = Logger::new(LogTime::now(), true);
+    logger.display_config = Some(DisplayConfig::default());
+
+    logger.color_mode = ColorMode::Always;
+    let colored = logger.add_log("warn".to_string(), component.clone(), Status::Warn, None);
+    assert!(colored.unwrap().contains(&format!("{}⚠ Warning", FG_YELLOW)));
+
+    logger.color_mode = ColorMode::Never;
+    let plain = logger.add_log("warn".to_string(), component, Status::Warn, None);
+    assert!(!plain.unwrap().contains('\x1b'));
+
+    // В файл цвета не попадают
+    logger.color_mode = ColorMode::Always;
+    let log_file = "/tmp/test_prettyterm_colors.log";
+    let _ = fs::remove_file(log_file);
+    logger.destroy(log_file, true, false).unwrap();
+    let content = fs::read_to_string(log_file).unwrap();
+    assert!(!content.contains('\x1b'));
+    let _ = fs::remove_file(log_file);
+}
+
 #[test]
 fn test_logger_creation() {
     let logger = Logger::new(LogTime::now(), true);
@@ -136,6 +382,79 @@ fn test_logger_multiple_logs() {
     assert_eq!(logger.logs.len(), 5);
 }
 
+#[test]
+fn test_logger_min_status() {
+    let mut logger = Logger::new(LogTime::now(), true);
+    let component = Component::new("test.rs".to_string(), "main".to_string(), "/src".to_string());
+    logger.min_status = Status::Info;
+
+    assert!(logger.add_log("noise".to_string(), component.clone(), Status::Debug, None).is_none());
+    assert!(logger.add_log("shown".to_string(), component.clone(), Status::Warn, None).is_some());
+    assert_eq!(logger.logs.len(), 1);
+
+    logger.filter_action = FilterAction::Suppress;
+    assert!(logger.add_log("kept".to_string(), component, Status::Trace, None).is_none());
+    assert_eq!(logger.logs.len(), 2);
+
+    let log_file = "/tmp/test_prettyterm_min_status.log";
+    let _ = fs::remove_file(log_file);
+    logger.destroy(log_file, true, false).unwrap();
+    let content = fs::read_to_string(log_file).unwrap();
+    assert!(content.contains("shown"));
+    assert!(!content.contains("kept"));
+    let _ = fs::remove_file(log_file);
+}
+
+#[test]
+fn test_log_filter_directives() {
+    let filter = LogFilter::parse("warn, parser=debug, codegen::emit=trace").unwrap();
+    let parser = Component::new("parser.rs".to_string(), "parse".to_string(), "src/frontend".to_string());
+    let emit = Component::new("emit.rs".to_string(), "write".to_string(), "src/codegen".to_string());
+    let lower = Component::new("lower.rs".to_string(), "run".to_string(), "src/codegen".to_string());
+
+    assert_eq!(filter.status_for(&parser), Some(Status::Debug));
+    assert_eq!(filter.status_for(&emit), Some(Status::Trace));
+    assert_eq!(filter.status_for(&lower), Some(Status::Warn));
+    assert_eq!(LogFilter::parse("parser.rs=error").unwrap().status_for(&parser), Some(Status::Error));
+    assert_eq!(LogFilter::parse("codegen").unwrap().status_for(&parser), None);
+
+    assert_eq!(
+        LogFilter::parse("parser=loud"),
+        Err(FilterParseError::InvalidStatus("parser=loud".to_string()))
+    );
+    assert!(matches!(LogFilter::parse("=debug"), Err(FilterParseError::EmptyTarget(_))));
+}
+
+#[test]
+fn test_logger_component_filter() {
+    let mut logger = Logger::new(LogTime::now(), false);
+    logger.min_status = Status::Error;
+    logger.filter = Some("emit=trace".parse().unwrap());
+
+    let emit = Component::new("emit.rs".to_string(), "write".to_string(), "src/codegen".to_string());
+    let lower = Component::new("lower.rs".to_string(), "run".to_string(), "src/codegen".to_string());
+    logger.add_log("emitting".to_string(), emit.clone(), Status::Trace, None);
+    logger.add_log("lowering".to_string(), lower.clone(), Status::Warn, None);
+
+    assert_eq!(logger.logs.len(), 1);
+    assert_eq!(logger.logs[0].message, "emitting");
+    assert!(logger.is_enabled(Status::Trace, &emit));
+    assert!(!logger.is_enabled(Status::Warn, &lower));
+
+    // Скрытые логи при выводе тоже проверяются по компоненту
+    logger.filter_action = FilterAction::Suppress;
+    assert_eq!(logger.add_log("lowering".to_string(), lower, Status::Warn, None), None);
+    assert_eq!(logger.logs.len(), 2);
+
+    let log_file = "/tmp/test_prettyterm_component_filter.log";
+    let _ = fs::remove_file(log_file);
+    logger.destroy(log_file, true, false).unwrap();
+    let content = fs::read_to_string(log_file).unwrap();
+    assert!(content.contains("emitting"));
+    assert!(!content.contains("lowering"));
+    let _ = fs::remove_file(log_file);
+}
+
 #[test]
 fn test_logger_destroy_without_file() {
     let mut logger = Logger::new(LogTime::now(), false);
@@ -167,6 +486,914 @@ fn test_logger_destroy_with_file() {
     let _ = fs::remove_file(log_file);
 }
 
+#[test]
+fn test_logger_destroy_file_policies() {
+    let dir = "/tmp/test_prettyterm_policies/nested";
+    let log_file = format!("{}/run.log", dir);
+    let _ = fs::remove_dir_all("/tmp/test_prettyterm_policies");
+    let component = Component::new("test.rs".to_string(), "main".to_string(), "/src".to_string());
+
+    let run = |message: &str, policy: FileOpenPolicy| {
+        let mut logger = Logger::new(LogTime::now(), false);
+        logger.file_policy = policy;
+        logger.add_log(message.to_string(), component.clone(), Status::Ok, None);
+        logger.destroy(&log_file, true, false)
+    };
+
+    // Каталоги создаются, записи дописываются и завершаются переводом строки
+    run("first", FileOpenPolicy::Append).unwrap();
+    run("second", FileOpenPolicy::Append).unwrap();
+    let content = fs::read_to_string(&log_file).unwrap();
+    assert_eq!(content.lines().count(), 2);
+    assert!(content.ends_with('\n'));
+
+    run("third", FileOpenPolicy::Truncate).unwrap();
+    let content = fs::read_to_string(&log_file).unwrap();
+    assert!(!content.contains("first"));
+    assert!(content.contains("third"));
+
+    let error = run("fourth", FileOpenPolicy::CreateNew).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::AlreadyExists);
+
+    run("fifth", FileOpenPolicy::Atomic).unwrap();
+    let content = fs::read_to_string(&log_file).unwrap();
+    assert_eq!(content.lines().count(), 1);
+    assert!(content.contains("fifth"));
+    assert_eq!(fs::read_dir(dir).unwrap().count(), 1);
+
+    // Неудачное переименование не оставляет временный файл
+    let blocked = format!("{}/blocked.log", dir);
+    fs::create_dir_all(format!("{}/inner", blocked)).unwrap();
+    let lines = vec!["sixth".to_string()];
+    assert!(write_log_file(std::path::Path::new(&blocked), FileOpenPolicy::Atomic, &lines).is_err());
+    assert_eq!(fs::read_dir(dir).unwrap().count(), 2);
+
+    let _ = fs::remove_dir_all("/tmp/test_prettyterm_policies");
+}
+
+#[test]
+fn test_rotating_file_sink_by_size() {
+    let dir = "/tmp/test_prettyterm_rotation_size";
+    let _ = fs::remove_dir_all(dir);
+    let log_file = format!("{}/build.log", dir);
+
+    let mut logger = Logger::new(LogTime::now(), false);
+    logger.style = LoggerPrintStyle::Custom(LogTemplate::parse("{message}").unwrap());
+    let policy = RotationPolicy {
+        max_bytes: Some(10),
+        keep: 2,
+        ..Default::default()
+    };
+    logger.add_sink(RotatingFileSink::open(&log_file, policy).unwrap());
+
+    let component = Component::new("test.rs".to_string(), "main".to_string(), "/src".to_string());
+    for i in 0..4 {
+        logger.add_log(format!("entry {}", i), component.clone(), Status::Info, None);
+    }
+    assert!(logger.sink_error.is_none());
+
+    assert_eq!(fs::read_to_string(&log_file).unwrap(), "entry 3\n");
+    assert_eq!(fs::read_to_string(format!("{}.1", log_file)).unwrap(), "entry 2\n");
+    assert_eq!(fs::read_to_string(format!("{}.2", log_file)).unwrap(), "entry 1\n");
+    assert!(!std::path::Path::new(&format!("{}.3", log_file)).exists());
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn test_rotating_file_sink_by_time() {
+    let dir = "/tmp/test_prettyterm_rotation_time";
+    let _ = fs::remove_dir_all(dir);
+    let log_file = format!("{}/daemon.log", dir);
+
+    let policy = RotationPolicy {
+        interval: Some(RotationInterval::Daily),
+        naming: RotationNaming::Dated,
+        keep: 1,
+        ..Default::default()
+    };
+    let mut sink = RotatingFileSink::open(&log_file, policy).unwrap();
+    fs::write(format!("{}.bak", log_file), "backup").unwrap();
+    fs::write(format!("{}.2025-03-01.lock", log_file), "").unwrap();
+
+    let day = |date: &str| LogTime::parse_rfc3339(&format!("{}T12:00:00Z", date)).unwrap();
+    sink.write_line("monday", &day("2025-03-03")).unwrap();
+    sink.write_line("tuesday", &day("2025-03-04")).unwrap();
+    sink.write_line("wednesday", &day("2025-03-05")).unwrap();
+
+    assert_eq!(fs::read_to_string(&log_file).unwrap(), "wednesday\n");
+    assert_eq!(fs::read_to_string(format!("{}.2025-03-04", log_file)).unwrap(), "tuesday\n");
+    // keep = 1: файл за понедельник удалён
+    assert!(!std::path::Path::new(&format!("{}.2025-03-03", log_file)).exists());
+    // Посторонние файлы с тем же префиксом остаются
+    assert!(std::path::Path::new(&format!("{}.bak", log_file)).exists());
+    assert!(std::path::Path::new(&format!("{}.2025-03-01.lock", log_file)).exists());
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_rotating_file_sink_gzip() {
+    let dir = "/tmp/test_prettyterm_rotation_gzip";
+    let _ = fs::remove_dir_all(dir);
+    let log_file = format!("{}/build.log", dir);
+
+    let policy = RotationPolicy {
+        compress: true,
+        ..Default::default()
+    };
+    let mut sink = RotatingFileSink::open(&log_file, policy).unwrap();
+    sink.write_line("compressed", &LogTime::now()).unwrap();
+    sink.rotate(&LogTime::now()).unwrap();
+
+    let compressed = fs::read(format!("{}.1.gz", log_file)).unwrap();
+    assert_eq!(&compressed[..2], &[0x1f, 0x8b]);
+    assert!(!std::path::Path::new(&format!("{}.1", log_file)).exists());
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn test_shared_logger_threads() {
+    let logger = SharedLogger::new(Logger::new(LogTime::now(), false));
+
+    let workers: Vec<_> = (0..4)
+        .map(|worker| {
+            let logger = logger.clone();
+            std::thread::Builder::new()
+                .name(format!("worker-{}", worker))
+                .spawn(move || {
+                    let component = Component::new("codegen.rs".to_string(), "emit".to_string(), "src".to_string());
+                    for i in 0..25 {
+                        logger.add_log(format!("unit {}", i), component.clone(), Status::Info, None);
+                    }
+                })
+                .unwrap()
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    let logger = logger.try_into_inner().ok().unwrap();
+    assert_eq!(logger.logs.len(), 100);
+    assert!(logger.logs.iter().all(|log| log.thread.as_deref().unwrap().starts_with("worker-")));
+
+    let sequences: Vec<u64> = logger.logs.iter().map(|log| log.sequence.unwrap()).collect();
+    assert_eq!(sequences, (0..100).collect::<Vec<u64>>());
+}
+
+#[test]
+fn test_shared_logger_is_send_sync() {
+    fn assert_send_sync<T: Send + Sync + Clone>() {}
+    assert_send_sync::<SharedLogger>();
+
+    let logger = SharedLogger::from(Logger::new(LogTime::now(), true));
+    let other = logger.clone();
+    let component = Component::new("main.rs".to_string(), "main".to_string(), "src".to_string());
+    let line = other.add_log("shared".to_string(), component, Status::Ok, None);
+    assert!(line.unwrap().contains("shared"));
+    assert_eq!(logger.lock().logs.len(), 1);
+    assert!(logger.try_into_inner().is_err());
+}
+
+/// Приёмник для тестов: медленно складывает строки в общий вектор
+struct SlowSink {
+    lines: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    delay: std::time::Duration,
+}
+
+impl LogSink for SlowSink {
+    fn write_log(&mut self, _log: &Log, line: &str) -> std::io::Result<()> {
+        std::thread::sleep(self.delay);
+        self.lines.lock().unwrap().push(line.to_string());
+        Ok(())
+    }
+}
+
+#[test]
+fn test_async_sink_drains_on_destroy() {
+    let lines = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = SlowSink {
+        lines: lines.clone(),
+        delay: std::time::Duration::from_millis(1),
+    };
+
+    let mut logger = Logger::new(LogTime::now(), false);
+    logger.add_sink(AsyncSink::new(sink, 4, OverflowPolicy::Block));
+    let component = Component::new("test.rs".to_string(), "main".to_string(), "/src".to_string());
+    for i in 0..20 {
+        logger.add_log(format!("entry {}", i), component.clone(), Status::Info, None);
+    }
+    logger.destroy("/tmp/unused.log", false, false).unwrap();
+
+    let lines = lines.lock().unwrap();
+    assert_eq!(lines.len(), 20);
+    assert!(lines[19].contains("entry 19"));
+}
+
+#[test]
+fn test_async_sink_drop_oldest() {
+    let lines = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = SlowSink {
+        lines: lines.clone(),
+        delay: std::time::Duration::from_millis(20),
+    };
+    let mut sink = AsyncSink::new(sink, 2, OverflowPolicy::DropOldest);
+
+    let component = Component::new("test.rs".to_string(), "main".to_string(), "/src".to_string());
+    for i in 0..10 {
+        let log = Log::new(Status::Info, format!("entry {}", i), component.clone(), LogTime::now());
+        sink.write_log(&log, &log.message).unwrap();
+    }
+    sink.flush().unwrap();
+
+    let dropped = sink.dropped();
+    let lines = lines.lock().unwrap();
+    assert!(dropped > 0);
+    assert_eq!(lines.len() as u64 + dropped, 10);
+    assert_eq!(lines.last().unwrap(), "entry 9");
+}
+
+#[test]
+fn test_async_sink_drop_newest() {
+    let lines = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = SlowSink {
+        lines: lines.clone(),
+        delay: std::time::Duration::from_millis(20),
+    };
+    let mut sink = AsyncSink::new(sink, 1, OverflowPolicy::DropNewest);
+
+    let component = Component::new("test.rs".to_string(), "main".to_string(), "/src".to_string());
+    for i in 0..5 {
+        let log = Log::new(Status::Info, format!("entry {}", i), component.clone(), LogTime::now());
+        sink.write_log(&log, &log.message).unwrap();
+    }
+    let dropped = sink.dropped();
+    drop(sink);
+
+    let lines = lines.lock().unwrap();
+    assert!(dropped > 0);
+    assert_eq!(lines.len() as u64 + dropped, 5);
+    assert_eq!(lines[0], "entry 0");
+}
+
+/// Приёмник для тестов, падающий на первой записи
+struct PanickingSink;
+
+impl LogSink for PanickingSink {
+    fn write_log(&mut self, _log: &Log, _line: &str) -> std::io::Result<()> {
+        panic!("sink failed");
+    }
+}
+
+#[test]
+fn test_async_sink_reports_stopped_writer() {
+    let mut sink = AsyncSink::new(PanickingSink, 1, OverflowPolicy::Block);
+    let component = Component::new("test.rs".to_string(), "main".to_string(), "/src".to_string());
+    let log = Log::new(Status::Info, "entry".to_string(), component, LogTime::now());
+
+    // Писатель упал: запись и сброс возвращают ошибку вместо бесконечного ожидания
+    let _ = sink.write_log(&log, "first");
+    assert!(sink.flush().is_err());
+    for _ in 0..3 {
+        let error = sink.write_log(&log, "next").unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::BrokenPipe);
+    }
+}
+
+#[test]
+fn test_logger_retention_last_entries() {
+    let lines = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut logger = Logger::new(LogTime::now(), false);
+    logger.retention = LogRetention::LastEntries(3);
+    logger.style = LoggerPrintStyle::Custom(LogTemplate::parse("{message}").unwrap());
+    logger.add_sink(SlowSink {
+        lines: lines.clone(),
+        delay: std::time::Duration::ZERO,
+    });
+
+    let component = Component::new("test.rs".to_string(), "main".to_string(), "/src".to_string());
+    for i in 0..10 {
+        logger.add_log(format!("entry {}", i), component.clone(), Status::Info, None);
+    }
+
+    // В памяти только последние записи, в приёмник попали все
+    let logs: &Vec<Log> = &logger.logs;
+    assert_eq!(logs.len(), 3);
+    assert_eq!(logger.evicted_logs, 7);
+    assert_eq!(logger.logs[0].message, "entry 7");
+    assert_eq!(lines.lock().unwrap().len(), 10);
+    assert_eq!(logger.dump_recent(2), "entry 8\nentry 9");
+    assert_eq!(logger.recent(100).count(), 3);
+}
+
+#[test]
+fn test_logger_retention_last_bytes() {
+    let mut logger = Logger::new(LogTime::now(), false);
+    let component = Component::new("test.rs".to_string(), "main".to_string(), "/src".to_string());
+    let sample = Log::new(Status::Info, "x".repeat(100), component.clone(), LogTime::now());
+    logger.retention = LogRetention::LastBytes(sample.estimated_size() * 5 / 2);
+
+    for _ in 0..10 {
+        logger.add_log("x".repeat(100), component.clone(), Status::Info, None);
+    }
+    assert!(logger.logs.len() <= 2 && !logger.logs.is_empty());
+    assert_eq!(logger.evicted_logs as usize + logger.logs.len(), 10);
+}
+
+fn query_fixture() -> Logger {
+    let mut logger = Logger::new(LogTime::now(), false);
+    let parser = Component::new("parser.rs".to_string(), "parse".to_string(), "src/frontend".to_string());
+    let lexer = Component::new("lexer.rs".to_string(), "tokenize".to_string(), "src/frontend/lex".to_string());
+    let codegen = Component::new("emit.rs".to_string(), "emit".to_string(), "src/backend".to_string());
+    let at = |minute: u32| Some(LogTime::parse_rfc3339(&format!("2025-03-07T10:{:02}:00Z", minute)).unwrap());
+
+    logger.add_log("unexpected token".to_string(), parser.clone(), Status::Error, at(1));
+    logger.add_log("deprecated syntax".to_string(), parser, Status::Warn, at(2));
+    logger.add_log("bad escape".to_string(), lexer, Status::Error, at(3));
+    logger.add_log("register spill".to_string(), codegen, Status::Error, at(4));
+    logger
+}
+
+#[test]
+fn test_log_query_filters() {
+    let logger = query_fixture();
+
+    let parser_errors = LogQuery::new().status(Status::Error).file("parser.rs");
+    let messages: Vec<&str> = logger.query(&parser_errors).map(|log| log.message.as_str()).collect();
+    assert_eq!(messages, vec!["unexpected token"]);
+
+    assert_eq!(logger.query(&LogQuery::new().dir("src/frontend")).count(), 3);
+    assert_eq!(logger.query(&LogQuery::new().dir("src/front")).count(), 0);
+    assert_eq!(logger.query(&LogQuery::new().func("emit")).count(), 1);
+    assert_eq!(logger.query(&LogQuery::new().min_status(Status::Warn)).count(), 4);
+    assert_eq!(logger.query(&LogQuery::new().contains("escape")).count(), 1);
+
+    let window = LogQuery::new()
+        .since(LogTime::parse_rfc3339("2025-03-07T10:02:00Z").unwrap())
+        .until(LogTime::parse_rfc3339("2025-03-07T10:04:00Z").unwrap());
+    assert_eq!(logger.query(&window).count(), 2);
+
+    let warn_or_spill = LogQuery::new().status(Status::Warn).status(Status::Error).contains("s");
+    assert_eq!(warn_or_spill.filter(logger.logs.iter()).count(), 3);
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn test_log_query_regex() {
+    let logger = query_fixture();
+    let query = LogQuery::new().matches_regex(r"^(bad|register) \w+$").unwrap();
+    assert_eq!(logger.query(&query).count(), 2);
+    assert!(LogQuery::new().matches_regex("(").is_err());
+}
+
+#[test]
+fn test_logger_summary() {
+    let mut logger = query_fixture();
+    logger.retention = LogRetention::LastEntries(1);
+    logger.add_log("done".to_string(), Component::new("main.rs".to_string(), "main".to_string(), "src".to_string()), Status::Ok, None);
+    assert_eq!(logger.logs.len(), 1);
+
+    // Итоги учитывают и вытесненные из памяти логи
+    let summary = logger.destroy("", false, false).unwrap();
+    assert_eq!(summary.total, 5);
+    assert_eq!(summary.errors(), 3);
+    assert_eq!(summary.warnings(), 1);
+    assert_eq!(summary.headline(), "3 errors, 1 warning emitted");
+    assert_eq!(summary.component_counts["src/frontend/parser.rs"], 2);
+    assert_eq!(summary.first_error.as_ref().unwrap().message, "unexpected token");
+    assert_eq!(summary.last_error.as_ref().unwrap().message, "register spill");
+
+    let text = summary.to_string();
+    assert!(text.contains("3 errors, 1 warning emitted"));
+    assert!(text.contains("Error 3 · Warning 1 · Ok 1"));
+    assert!(!text.contains("\x1b["));
+    assert!(summary.render(Some(&DisplayConfig::default())).contains("\x1b["));
+    assert!(summary.to_json().starts_with("{\"total\":5,\"errors\":3,\"warnings\":1,"));
+}
+
+#[test]
+fn test_logger_dedup_consecutive() {
+    let lines = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut logger = Logger::new(LogTime::now(), true);
+    logger.color_mode = ColorMode::Never;
+    logger.dedup = DedupPolicy::Consecutive;
+    logger.add_sink(SlowSink { lines: lines.clone(), delay: std::time::Duration::ZERO });
+    let capture = logger.capture();
+    let component = Component::new("loop.rs".to_string(), "run".to_string(), "src".to_string());
+
+    assert!(logger.add_log("disk is full".to_string(), component.clone(), Status::Warn, None).is_some());
+    for _ in 0..1523 {
+        assert!(logger.add_log("disk is full".to_string(), component.clone(), Status::Warn, None).is_none());
+    }
+    let output = logger.add_log("done".to_string(), component, Status::Ok, None).unwrap();
+    assert!(output.starts_with("Warning: disk is full (repeated 1523 times)"));
+    assert!(output.contains("\nOk: done"));
+
+    assert_eq!(logger.logs.len(), 2);
+    assert_eq!(logger.logs[0].repeated, 1523);
+    assert_eq!(logger.summary().warnings(), 1524);
+
+    let lines = lines.lock().unwrap();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[1].contains("disk is full (repeated 1523 times)"));
+
+    // Строка о повторах получает свой номер
+    let sequences: Vec<Option<u64>> = capture.logs().iter().map(|log| log.sequence).collect();
+    assert_eq!(sequences, vec![Some(0), Some(1), Some(2)]);
+}
+
+#[test]
+fn test_logger_dedup_window_and_rate_limit() {
+    let at = |second: u32| Some(LogTime::parse_rfc3339(&format!("2025-03-07T10:00:{:02}Z", second)).unwrap());
+    let component = Component::new("net.rs".to_string(), "poll".to_string(), "src".to_string());
+
+    let mut logger = Logger::new(LogTime::now(), false);
+    logger.dedup = DedupPolicy::Window(chrono::TimeDelta::seconds(10));
+    logger.add_log("timeout".to_string(), component.clone(), Status::Error, at(0));
+    logger.add_log("retrying".to_string(), component.clone(), Status::Info, at(1));
+    logger.add_log("timeout".to_string(), component.clone(), Status::Error, at(2));
+    logger.add_log("timeout".to_string(), component.clone(), Status::Error, at(20));
+    let messages: Vec<String> = logger.logs.iter().map(|log| log.display_message().into_owned()).collect();
+    assert_eq!(messages, vec!["timeout (repeated 1 time)", "retrying", "timeout"]);
+
+    let mut logger = Logger::new(LogTime::now(), false);
+    logger.rate_limit = Some(RateLimit { max_logs: 2, per: chrono::TimeDelta::seconds(1) });
+    for second in [0, 0, 0, 0, 1] {
+        logger.add_log(format!("packet {}", second), component.clone(), Status::Trace, at(second));
+    }
+    assert_eq!(logger.logs.len(), 3);
+    assert_eq!(logger.rate_limited_logs, 2);
+}
+
+#[test]
+fn test_log_parser_round_trip() {
+    let time = LogTime::parse_rfc3339("2025-03-07T10:15:30.250+03:00").unwrap();
+    let component = Component::new("parser.rs".to_string(), "parse".to_string(), "src/frontend".to_string());
+    let mut log = Log::new(Status::Custom("Build"), "unexpected token".to_string(), component, time)
+        .with_field("path", "a b.rs")
+        .with_field("count", 3);
+    log.repeated = 2;
+
+    for style in [LoggerPrintStyle::Tiny, LoggerPrintStyle::Flat, LoggerPrintStyle::Full, LoggerPrintStyle::Json, LoggerPrintStyle::Logfmt] {
+        let mut logger = Logger::new(time, false);
+        logger.style = style.clone();
+        logger.time_format = TimeFormat::Precise;
+        let text = logger.format_log(&log);
+
+        let parser = LogParser::detect(&text).unwrap().with_base_time(time);
+        let parsed = parser.parse_entry(&text).unwrap_or_else(|error| panic!("{:?}: {}", style, error));
+        assert_eq!(parsed.status, Status::Custom("Build"), "{:?}", style);
+        assert_eq!(parsed.message, "unexpected token", "{:?}", style);
+        assert_eq!(parsed.repeated, 2, "{:?}", style);
+        assert_eq!(parsed.time, time, "{:?}", style);
+        assert_eq!(parsed.component.file_name, "parser.rs", "{:?}", style);
+        assert_eq!(parsed.field("path"), Some(&FieldValue::from("a b.rs")), "{:?}", style);
+        assert_eq!(parsed.field("count"), Some(&FieldValue::Int(3)), "{:?}", style);
+    }
+}
+
+#[test]
+fn test_log_parser_ambiguous_text() {
+    let time = LogTime::parse_rfc3339("2025-03-07T10:15:30+03:00").unwrap();
+    let component = Component::new("main.rs".to_string(), "code-gen".to_string(), "src".to_string());
+
+    // Текст вида `key=value` в сообщении и `-` в имени функции не теряются
+    for (message, fields) in [("set x=1", 0), ("set x=1", 1), ("\"quoted\" text", 0), ("plain", 1)] {
+        let mut log = Log::new(Status::Info, message.to_string(), component.clone(), time);
+        if fields > 0 {
+            log = log.with_field("id", 7);
+        }
+        for style in [LoggerPrintStyle::Tiny, LoggerPrintStyle::Flat, LoggerPrintStyle::Full] {
+            let text = log.format(&style);
+            let parsed = LogParser::new(style.clone()).with_base_time(time).parse_entry(&text).unwrap();
+            assert_eq!(parsed.message, message, "{:?}: {}", style, text);
+            assert_eq!(parsed.fields.len(), fields, "{:?}: {}", style, text);
+            if matches!(style, LoggerPrintStyle::Tiny | LoggerPrintStyle::Full) {
+                assert_eq!(parsed.component.file_name, "main.rs", "{:?}: {}", style, text);
+                assert_eq!(parsed.component.func_name, "code-gen", "{:?}: {}", style, text);
+            }
+        }
+    }
+    assert_eq!(
+        Log::new(Status::Info, "set x=1".to_string(), component, time).format(&LoggerPrintStyle::Flat),
+        "Info: \"set x=1\" | file main.rs | time 10:15:30"
+    );
+
+    // Суррогатная пара в JSON — один символ
+    let json = r#"{"time":"2025-03-07T10:00:00Z","status":"Info","message":"ok \uD83D\uDE00 \u00e9","component":{"file":"a.rs"}}"#;
+    let log = LogParser::new(LoggerPrintStyle::Json).parse_entry(json).unwrap();
+    assert_eq!(log.message, "ok 😀 é");
+}
+
+#[test]
+fn test_log_parser_errors() {
+    let parser = LogParser::new(LoggerPrintStyle::Full);
+    let text = "[Error|10:00:00][src/main.rs-main]: failed\n    code = 2\n\ngarbage\n[Info|25:99:00][src/main.rs-main]: late\n";
+    let results = parser.parse_str(text);
+    assert_eq!(results.len(), 3);
+
+    let log = results[0].as_ref().unwrap();
+    assert_eq!(log.status, Status::Error);
+    assert_eq!(log.component.dir_path, "src");
+    assert_eq!(log.field("code"), Some(&FieldValue::Int(2)));
+    assert!(matches!(results[1], Err(LogParseError::Malformed { line: 4, .. })));
+    assert!(matches!(&results[2], Err(LogParseError::InvalidTime { line: 5, time }) if time == "25:99:00"));
+
+    let json = LogParser::new(LoggerPrintStyle::Json);
+    assert!(json.parse_entry("{\"time\":").unwrap_err().to_string().starts_with("line 1: malformed log entry"));
+    assert!(LogParser::new(LoggerPrintStyle::Tiny).parse_entry("Bad status: x | from a.rs-func:f, time is 10:00:00").is_err());
+    assert_eq!(Status::from_name("Deploy"), Status::from_name("Deploy"));
+    assert_eq!(Status::from_name("warning"), Ok(Status::Warn));
+    assert!(Status::from_name("not-a-status!").is_err());
+    assert!(Status::from_name(&"x".repeat(33)).is_err());
+    assert!(matches!(
+        LogParser::new(LoggerPrintStyle::Tiny).parse_entry("#?!: x | from a.rs-func:f, time is 10:00:00"),
+        Err(LogParseError::InvalidStatus { .. })
+    ));
+
+    // Глубокая вложенность — ошибка, а не переполнение стека
+    let nested = format!(
+        "{{\"time\":\"2025-03-07T10:00:00Z\",\"status\":\"Info\",\"message\":\"x\",\"fields\":{{\"a\":{}}}}}",
+        "[".repeat(100_000)
+    );
+    assert!(json.parse_entry(&nested).unwrap_err().to_string().contains("nesting deeper than"));
+}
+
+#[test]
+fn test_log_viewer_binary() {
+    let path = std::env::temp_dir().join(format!("prettyterm_viewer_{}.log", std::process::id()));
+    fs::write(
+        &path,
+        "[Error|10:00:00][src/frontend/parser.rs-parse]: unexpected token\n    line = 12\n[Info|10:00:05][src/main.rs-main]: started\nnot a log\n",
+    )
+    .unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_prettyterm-log"))
+        .args(["--min-status", "warn", "--style", "json", "--color", "never"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(stdout.contains("\"message\":\"unexpected token\""));
+    assert!(stdout.contains("\"fields\":{\"line\":12}"));
+    assert!(String::from_utf8(output.stderr).unwrap().contains("line 4: malformed log entry"));
+}
+
+#[test]
+fn test_log_merger_orders_and_tags() {
+    let at = |second: u32| LogTime::parse_rfc3339(&format!("2025-03-07T10:00:{:02}Z", second)).unwrap();
+    let component = Component::new("worker.rs".to_string(), "run".to_string(), "src".to_string());
+    let log = |message: &str, second: u32| Log::new(Status::Info, message.to_string(), component.clone(), at(second));
+
+    let dir = std::env::temp_dir().join(format!("prettyterm_merge_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("worker-2.log");
+    fs::write(&file, format!("{}\n{}\n", log_to_json(&log("w2 first", 1)), log_to_json(&log("w2 tie", 3)))).unwrap();
+
+    let merger = LogMerger::new()
+        .source(LogSource::new("worker-1", vec![log("w1 tie", 3), log("w1 first", 0).with_field("source", "cache"), log("w1 tie again", 3)]))
+        .source(LogSource::read(&file).unwrap());
+    let merged = merger.merge();
+
+    let messages: Vec<&str> = merged.iter().map(|log| log.message.as_str()).collect();
+    assert_eq!(messages, vec!["w1 first", "w2 first", "w1 tie", "w1 tie again", "w2 tie"]);
+    assert_eq!(merged[1].field("source"), Some(&FieldValue::from("worker-2.log")));
+    // Собственное поле `source` не затирается именем источника
+    assert_eq!(merged[0].field("source"), Some(&FieldValue::from("worker-1")));
+    assert_eq!(merged[0].field("field.source"), Some(&FieldValue::from("cache")));
+
+    let output = dir.join("combined.log");
+    merger.write(&output, &LoggerPrintStyle::Logfmt, FileOpenPolicy::Truncate).unwrap();
+    let combined = fs::read_to_string(&output).unwrap();
+    assert_eq!(combined.lines().count(), 5);
+    assert!(combined.lines().next().unwrap().ends_with("source=worker-1"));
+
+    // Время без даты и `--since` относятся ко дню изменения файлов, а не к сегодняшнему
+    let modified = LogTime::parse_rfc3339("2024-01-02T12:00:00Z").unwrap();
+    let mut paths = Vec::new();
+    for (name, line) in [("a.log", "[Info|10:00:00][src/a.rs-run]: early"), ("b.log", "[Info|10:00:05][src/b.rs-run]: late")] {
+        let path = dir.join(name);
+        fs::write(&path, format!("{}\n", line)).unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(modified.datetime.into())
+            .unwrap();
+        paths.push(path);
+    }
+    let source = LogSource::read_at(&paths[0], modified).unwrap();
+    assert_eq!(source.logs[0].time.datetime.date_naive(), modified.datetime.date_naive());
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_prettyterm-log"))
+        .args(["--merge", "--since", "10:00:03", "--style", "logfmt", "--color", "never"])
+        .args(&paths)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 1, "{}", stdout);
+    assert!(stdout.contains("msg=late"));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[derive(Debug)]
+struct ChainError {
+    message: &'static str,
+    source: Option<Box<ChainError>>,
+}
+
+impl std::fmt::Display for ChainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ChainError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_deref().map(|source| source as &(dyn std::error::Error + 'static))
+    }
+}
+
+#[test]
+fn test_log_error_chain() {
+    let error = ChainError {
+        message: "failed to build project",
+        source: Some(Box::new(ChainError {
+            message: "failed to parse src/main.rs",
+            source: Some(Box::new(ChainError { message: "unexpected token", source: None })),
+        })),
+    };
+    assert_eq!(
+        format_error_chain(&error, BranchStyle::Unicode),
+        "failed to build project\n  ├─ caused by: failed to parse src/main.rs\n  ╰─ caused by: unexpected token"
+    );
+    assert!(format_error_chain(&error, BranchStyle::Indent).ends_with("\n  caused by: unexpected token"));
+
+    let mut logger = Logger::new(LogTime::now(), false);
+    let component = Component::new("main.rs".to_string(), "build".to_string(), "src".to_string());
+    let at = LogTime::parse_rfc3339("2025-03-07T14:30:45+03:00").unwrap();
+    assert_eq!(ErrorReport::new().to_log(&error, component.clone(), at).time, at);
+    logger.log_error(&error, component.clone());
+    logger.log_error_with(&error, component, &ErrorReport::new().status(Status::Fatal).backtrace(true));
+
+    assert_eq!(logger.logs[0].status, Status::Error);
+    assert!(logger.logs[0].message.starts_with("failed to build project\n"));
+    assert!(logger.logs[0].field("backtrace").is_none());
+    assert_eq!(logger.logs[1].status, Status::Fatal);
+    assert!(logger.logs[1].field("backtrace").is_some());
+
+    // Строки причин с отступом читаются обратно как часть сообщения
+    let mut chained = logger.logs[0].clone();
+    chained.fields.push(("path".to_string(), FieldValue::from("src/main.rs")));
+    let next = Log::new(Status::Info, "retrying".to_string(), chained.component.clone(), chained.time);
+    for style in [LoggerPrintStyle::Tiny, LoggerPrintStyle::Flat, LoggerPrintStyle::Full] {
+        logger.style = style.clone();
+        let text = format!("{}\n{}\n", logger.format_log(&chained), logger.format_log(&next));
+        let detected = LogParser::detect(&text).map(|parser| parser.style);
+        assert_eq!(format!("{:?}", detected), format!("{:?}", Some(&style)));
+        let parsed: Vec<Log> = LogParser::new(style).parse_str(&text).into_iter().map(Result::unwrap).collect();
+        assert_eq!(parsed.len(), 2, "{}", text);
+        assert_eq!(parsed[0].message, chained.message);
+        assert_eq!(parsed[0].field("path"), Some(&FieldValue::from("src/main.rs")));
+        assert_eq!(parsed[1].message, "retrying");
+    }
+}
+
+#[test]
+fn test_logger_spans_nest() {
+    let mut logger = Logger::new(LogTime::now(), true);
+    logger.style = LoggerPrintStyle::Flat;
+    logger.color_mode = ColorMode::Never;
+    let mut output = Vec::new();
+    {
+        let mut build = logger.span("build");
+        output.extend(build.take_output());
+        {
+            let mut parse = build.span("parse");
+            output.extend(parse.take_output());
+            output.extend(parse.add_log("3 files".to_string(), Component::new("parser.rs".to_string(), "parse".to_string(), "src".to_string()), Status::Info, None));
+            parse.set_status(Status::Warn);
+            output.extend(parse.end());
+        }
+        build.span("codegen").finish(Status::Error);
+    }
+
+    // Сообщения хранятся без отступов, глубина — отдельно
+    let messages: Vec<&str> = logger.logs.iter().map(|log| log.message.as_str()).collect();
+    assert_eq!(messages[0], "build");
+    assert_eq!(messages[1], "parse");
+    assert_eq!(messages[2], "3 files");
+    assert!(messages[3].starts_with("parse finished in "));
+    assert!(messages[5].starts_with("codegen finished in "));
+    assert!(messages[6].starts_with("build finished in "));
+    let depths: Vec<usize> = logger.logs.iter().map(|log| log.span_depth).collect();
+    assert_eq!(depths, vec![0, 1, 2, 1, 1, 1, 0]);
+    assert_eq!(logger.logs[0].span_edge, Some(SpanEdge::Start));
+    assert_eq!(logger.logs[2].span_edge, None);
+    assert_eq!(logger.logs[6].span_edge, Some(SpanEdge::End));
+
+    // Отступ добавляется только при выводе в терминал
+    assert!(output[0].starts_with("Info: ╭─ build span=build | "));
+    assert!(output[1].starts_with("Info: │  ╭─ parse span=parse | "));
+    assert!(output[2].starts_with("Info: │  │  3 files | "));
+    assert!(output[3].starts_with("Warning: │  ╰─ parse finished in "));
+    assert!(!logger.format_log(&logger.logs[2]).contains('│'));
+    logger.style = LoggerPrintStyle::Json;
+    assert!(logger.format_for_terminal(&logger.logs[2]).contains("\"message\":\"3 files\""));
+
+    let statuses: Vec<Status> = logger.logs.iter().map(|log| log.status).collect();
+    assert_eq!(statuses, vec![Status::Info, Status::Info, Status::Info, Status::Warn, Status::Info, Status::Error, Status::Ok]);
+    assert_eq!(logger.logs[0].component.file_name, "integration_tests.rs");
+    assert_eq!(logger.logs[0].component.func_name, "build");
+    assert!(logger.logs[6].field("elapsed_ms").is_some());
+
+    // Глубина восстанавливается после закрытия этапов
+    drop(logger.span("link"));
+    assert_eq!(logger.logs[7].span_depth, 0);
+}
+
+#[test]
+fn test_child_logger_attaches_context() {
+    let parser = Component::new("parser.rs".to_string(), "parse".to_string(), "src".to_string());
+    let lexer = Component::new("lexer.rs".to_string(), "lex".to_string(), "src".to_string());
+
+    let mut logger = Logger::new(LogTime::now(), false);
+    {
+        let mut child = logger.child(parser, fields!("unit" => "main.rs", "pass" => 1));
+        child.add_log("parsed".to_string(), Status::Ok);
+        child.add_log_with_fields("slow".to_string(), Status::Warn, fields!("ms" => 250));
+
+        let mut nested = child.child(lexer.clone(), fields!("pass" => 2));
+        nested.add_log("tokens".to_string(), Status::Info);
+    }
+
+    assert_eq!(logger.logs.len(), 3);
+    assert_eq!(logger.logs[0].component.file_name, "parser.rs");
+    assert_eq!(logger.logs[0].fields, fields!("unit" => "main.rs", "pass" => 1));
+    assert_eq!(logger.logs[1].fields, fields!("unit" => "main.rs", "pass" => 1, "ms" => 250));
+    assert_eq!(logger.logs[2].component.file_name, "lexer.rs");
+    assert_eq!(logger.logs[2].fields, fields!("unit" => "main.rs", "pass" => 2));
+
+    let shared = SharedLogger::new(Logger::new(LogTime::now(), false));
+    let worker = shared.child(lexer, fields!("worker" => 3));
+    std::thread::spawn(move || worker.add_log("done".to_string(), Status::Ok)).join().unwrap();
+    assert_eq!(shared.lock().logs[0].field("worker"), Some(&FieldValue::Int(3)));
+}
+
+#[test]
+fn test_log_capture_and_frozen_time() {
+    let mut logger = Logger::new(LogTime::now(), false);
+    let frozen = logger.freeze_time(LogTime::parse_rfc3339("2025-03-07T14:30:45.250+03:00").unwrap());
+    let capture = logger.capture();
+    let component = Component::new("parser.rs".to_string(), "parse".to_string(), "src".to_string());
+
+    logger.add_log("deprecated syntax".to_string(), component.clone(), Status::Warn, None);
+    frozen.advance(chrono::TimeDelta::seconds(2));
+    logger.add_log("parsed".to_string(), component, Status::Ok, None);
+
+    assert_logged!(capture, Status::Warn, contains "deprecated");
+    assert_logged!(capture, contains "parsed");
+    assert_logged!(capture, Status::Ok);
+    assert_not_logged!(capture, Status::Error);
+    assert_eq!(capture.count(Some(Status::Warn), None), 1);
+    assert_eq!(
+        capture.lines(),
+        vec![
+            "Warning: deprecated syntax | from parser.rs-func:parse, time is 14:30:45",
+            "Ok: parsed | from parser.rs-func:parse, time is 14:30:47",
+        ]
+    );
+
+    let missing = std::panic::catch_unwind(|| assert_logged!(capture, Status::Error, contains "oops"));
+    let message = missing.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("expected a log with status Error containing \"oops\""));
+    assert!(message.contains("Ok: parsed"));
+
+    // Время принадлежит логгеру, поэтому действует и для логов из других потоков
+    let shared = SharedLogger::new(logger);
+    let worker = shared.clone();
+    std::thread::spawn(move || {
+        worker.add_log("from worker".to_string(), Component::new("worker.rs".to_string(), "run".to_string(), "src".to_string()), Status::Info, None)
+    })
+    .join()
+    .unwrap();
+    assert_eq!(capture.logs().last().unwrap().time.format(), "14:30:47");
+
+    shared.lock().unfreeze_time();
+    assert_ne!(shared.lock().now().date(), LogTime::parse_rfc3339("2025-03-07T00:00:00Z").unwrap().date());
+}
+
+#[test]
+fn test_logger_manual_clock() {
+    let start = LogTime::parse_rfc3339("2025-03-07T14:30:45+03:00").unwrap();
+    let clock = ManualClock::new(start);
+    let mut logger = Logger::new(start, false);
+    logger.set_clock(clock.clone());
+    let capture = logger.capture();
+    let component = Component::new("build.rs".to_string(), "main".to_string(), "src".to_string());
+
+    logger.add_log("started".to_string(), component.clone(), Status::Info, None);
+    {
+        let _span = logger.span_with_component("compile", component.clone());
+        clock.advance(chrono::TimeDelta::milliseconds(1500));
+    }
+    clock.set(LogTime::parse_rfc3339("2025-03-07T14:32:00+03:00").unwrap());
+    logger.add_log("done".to_string(), component, Status::Ok, None);
+
+    let times: Vec<String> = capture.logs().iter().map(|log| log.time.format()).collect();
+    assert_eq!(times, vec!["14:30:45", "14:30:45", "14:30:46", "14:32:00"]);
+    assert_logged!(capture, contains "compile finished in 1.500s");
+
+    let summary = logger.finish("", false, false).unwrap();
+    assert_eq!(summary.runtime(), chrono::TimeDelta::seconds(75));
+
+    logger.set_clock(FixedClock(start));
+    assert_eq!(logger.now(), start);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_syslog_sink_sends_rfc5424() {
+    use std::os::unix::net::UnixDatagram;
+
+    let socket_path = "/tmp/test_prettyterm_syslog.sock";
+    let _ = fs::remove_file(socket_path);
+    let listener = UnixDatagram::bind(socket_path).unwrap();
+
+    let mut logger = Logger::new(LogTime::now(), false);
+    let sink = SyslogSink::connect_to(socket_path)
+        .unwrap()
+        .facility(SyslogFacility::Daemon)
+        .app_name("builder")
+        .hostname("build host")
+        .enterprise_id(32473);
+    logger.add_sink(sink);
+
+    let time = LogTime::parse_rfc3339("2025-03-07T14:30:45.250+03:00").unwrap();
+    let component = Component::new("parser.rs".to_string(), "parse".to_string(), "src".to_string()).with_line(7);
+    logger.add_log_with_fields(
+        "unexpected token".to_string(),
+        component.clone(),
+        Status::Error,
+        Some(time),
+        vec![("path".to_string(), "src/\"main\".rs".into()), ("line".to_string(), 42.into())],
+    );
+    logger.add_log("finished".to_string(), component.clone(), Status::Ok, Some(time));
+
+    let mut buffer = [0u8; 1024];
+    let size = listener.recv(&mut buffer).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&buffer[..size]).unwrap(),
+        format!(
+            "<27>1 2025-03-07T14:30:45.250+03:00 build_host builder {} parser.rs [component@32473 file=\"parser.rs\" func=\"parse\" dir=\"src\" line=\"7\"][fields@32473 path=\"src/\\\"main\\\".rs\" line=\"42\"] unexpected token",
+            std::process::id()
+        )
+    );
+    let size = listener.recv(&mut buffer).unwrap();
+    assert!(std::str::from_utf8(&buffer[..size]).unwrap().starts_with("<29>1 "));
+
+    // Без номера предприятия structured data не пишется, длинный текст обрезается
+    let sink = SyslogSink::connect_to(socket_path)
+        .unwrap()
+        .facility(SyslogFacility::Local7)
+        .app_name("builder")
+        .hostname("host")
+        .max_message_len(120);
+    let long = Log::new(Status::Info, "ж".repeat(200), component, time);
+    let message = sink.format(&long);
+    assert!(message.starts_with("<190>1 2025-03-07T14:30:45.250+03:00 host builder "));
+    assert!(message.contains(" parser.rs - жж"));
+    assert!(message.len() <= 120 && message.len() >= 119);
+
+    let mut sink = sink.max_message_len(1 << 20);
+
+    // Без номера предприятия компонент и поля дописываются к тексту парами logfmt
+    let with_fields = Log::new(Status::Info, "built".to_string(), long.component.clone(), time)
+        .with_field("path", "a b")
+        .with_field("line", 42);
+    assert!(sink
+        .format(&with_fields)
+        .ends_with(" parser.rs - built func=parse dir=src line=7 path=\"a b\" field.line=42"));
+
+    // Датаграмму больше лимита сокета отправляем повторно в 2048 байт
+    let huge = Log::new(Status::Info, "x".repeat(1 << 20), long.component.clone(), time);
+    sink.write_log(&huge, "").unwrap();
+    let mut buffer = vec![0u8; 4096];
+    assert_eq!(listener.recv(&mut buffer).unwrap(), 2048);
+
+    assert_eq!(syslog_severity(Status::Fatal), 2);
+    assert_eq!(syslog_severity(Status::Custom("Note")), 6);
+    assert_eq!(syslog_severity(Status::Trace), 7);
+    let _ = fs::remove_file(socket_path);
+}
+
 #[test]
 fn test_stylish_simple_tag() {
     let result = process_style_tags("<red>Hello</red>");
@@ -304,6 +1531,16 @@ fn test_icons_theme_default() {
     assert_eq!(theme.warning_icon, "⚠");
 }
 
+#[test]
+fn test_theme_for_status() {
+    let colors = ColorTheme::default();
+    let icons = IconsTheme::default();
+    assert_eq!(colors.for_status(Status::Fatal).as_str(), FG_RED);
+    assert_eq!(colors.for_status(Status::Info).as_str(), FG_BLUE);
+    assert_eq!(icons.for_status(Status::Warn), "⚠");
+    assert_eq!(icons.for_status(Status::Ok), "✓");
+}
+
 #[test]
 fn test_display_config_default() {
     let config = DisplayConfig::default();
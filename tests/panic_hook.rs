@@ -0,0 +1,54 @@
+//! Tests for PanicHook
+//!
+//! The hook is global for the process, so these tests live in their own binary.
+
+use prettyterm::*;
+use std::fs;
+use std::panic;
+use std::sync::Arc;
+
+#[test]
+fn test_panic_hook_logs_fatal() {
+    let path = std::env::temp_dir().join(format!("prettyterm_panic_{}.log", std::process::id()));
+    let mut logger = Logger::new(LogTime::now(), false);
+    logger.file_policy = FileOpenPolicy::Truncate;
+    // Лог о панике проходит мимо ограничения частоты, хотя лимит компонента уже исчерпан
+    logger.rate_limit = Some(RateLimit { max_logs: 1, per: chrono::TimeDelta::seconds(60) });
+    let shared = SharedLogger::new(logger);
+    shared.add_log("compiling".to_string(), Component::new("panic_hook.rs".to_string(), "panic".to_string(), "tests".to_string()), Status::Info, None);
+
+    // Исходный обработчик восстанавливается после теста
+    let original = Arc::new(panic::take_hook());
+    let saved = original.clone();
+    panic::set_hook(Box::new(move |info| saved(info)));
+
+    PanicHook::new().backtrace(true).log_file(&path).install(&shared);
+    let result = std::thread::spawn(|| panic!("index out of bounds: {}", 7)).join();
+    let _ = panic::take_hook();
+    panic::set_hook(Box::new(move |info| original(info)));
+    assert!(result.is_err());
+
+    {
+        let logger = shared.lock();
+        let fatal = logger
+            .logs
+            .iter()
+            .find(|log| log.message == "index out of bounds: 7")
+            .expect("panic was not logged");
+        assert_eq!(fatal.status, Status::Fatal);
+        assert_eq!(fatal.component.file_name, "panic_hook.rs");
+        assert_eq!(fatal.component.dir_path, "tests");
+        assert!(fatal.component.line.is_some());
+        assert!(fatal.field("backtrace").is_some());
+    }
+
+    // Обычное завершение после паники дописывает только новые логи
+    shared.add_log("cleanup".to_string(), Component::new("main.rs".to_string(), "main".to_string(), "src".to_string()), Status::Info, None);
+    shared.finish(&path.to_string_lossy(), true, false).unwrap();
+
+    let written = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(written.matches("compiling").count(), 1);
+    assert_eq!(written.matches("Fatal: index out of bounds: 7").count(), 1);
+    assert_eq!(written.matches("cleanup").count(), 1);
+}